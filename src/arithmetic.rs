@@ -1,11 +1,11 @@
 use std::ops::{
-    Add, Sub, Mul, Div,
-    AddAssign, SubAssign, MulAssign, DivAssign
+    Add, Sub, Mul, Div, Rem, Neg, Not,
+    BitAnd, BitOr, BitXor, Shl, Shr
 };
 
 use crate::*;
 
-// int vips_sum()
+define_array_operator!(sum);
 define_operator!(add, "left", struct Args<'a> {
     pub right: &'a VipsImage
 });
@@ -19,147 +19,225 @@ define_operator!(divide, "left", struct Args<'a> {
     pub right: &'a VipsImage
 });
 
-// TODO: resolve naming conflict
-// impl Add for &VipsImage {
-//     type Output = VipsImage;
-//     fn add(self, rhs: Self) -> Self::Output {
-//         self.add(add::OpArgs{right: &rhs}).unwrap_or_default()
-//     }
-// }
-impl AddAssign for &mut VipsImage {
-    fn add_assign(&mut self, rhs: Self) {
-        **self = self.add(add::OpArgs{right: &rhs}).unwrap_or_default();
+// Operator overloads
+//
+// Operations can fail, so the operators return a `Result` instead of an image:
+//
+// ```ignore
+// let out = (&(&img - 30.)? * 1.5)?;
+// ```
+//
+// The trait methods `add` and friends are shadowed by the inherent operator
+// methods of the same name, so the impls call the inherent methods with
+// explicit paths.
+
+impl Add<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn add(self, rhs: &VipsImage) -> Self::Output {
+        VipsImage::add(self, add::OpArgs{right: rhs})
     }
 }
 impl Sub<&VipsImage> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn sub(self, rhs: &VipsImage) -> Self::Output {
-        self.subtract(subtract::OpArgs{right: &rhs}).unwrap_or_default()
-    }
-}
-impl SubAssign<&VipsImage> for &mut VipsImage {
-    fn sub_assign(&mut self, rhs: &VipsImage){
-        **self = self.sub(rhs);
+        VipsImage::subtract(self, subtract::OpArgs{right: rhs})
     }
 }
 impl Mul<&VipsImage> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn mul(self, rhs: &VipsImage) -> Self::Output {
-        self.multiply(multiply::OpArgs{right: &rhs}).unwrap_or_default()
-    }
-}
-impl MulAssign<&VipsImage> for &mut VipsImage {
-    fn mul_assign(&mut self, rhs: &VipsImage){
-        **self = self.mul(rhs);
+        VipsImage::multiply(self, multiply::OpArgs{right: rhs})
     }
 }
 impl Div<&VipsImage> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn div(self, rhs: &VipsImage) -> Self::Output {
-        self.divide(divide::OpArgs{right: &rhs}).unwrap_or_default()
-    }
-}
-impl DivAssign<&VipsImage> for &mut VipsImage {
-    fn div_assign(&mut self, rhs: &VipsImage){
-        **self = self.div(rhs);
+        VipsImage::divide(self, divide::OpArgs{right: rhs})
     }
 }
 
-// TODO: resolve naming conflict
-// impl Add<&[f64]> for &VipsImage {
-//     type Output = VipsImage;
-//     fn add(self, rhs: &[f64]) -> Self::Output {
-//         self.linear(linear::OpArgs{a: 1, b: &rhs}).unwrap_or_default()
-//     }
-// }
-impl AddAssign<&[f64]> for &mut VipsImage {
-    fn add_assign(&mut self, rhs: &[f64]) {
+impl Add<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn add(self, rhs: &[f64]) -> Self::Output {
         let one: Vec<f64> = rhs.iter().map(|_| 1f64).collect();
-        **self = self.linear(linear::OpArgs{a: &one, b: rhs}).unwrap_or_default();
+        self.linear(linear::OpArgs{a: &one, b: rhs})
     }
 }
 impl Sub<&[f64]> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn sub(self, rhs: &[f64]) -> Self::Output {
         let one: Vec<f64> = rhs.iter().map(|_| 1f64).collect();
         let inv: Vec<f64> = rhs.iter().map(|x| -x).collect();
-        self.linear(linear::OpArgs{a: &one, b: &inv}).unwrap_or_default()
-    }
-}
-impl SubAssign<&[f64]> for &mut VipsImage {
-    fn sub_assign(&mut self, rhs: &[f64]){
-        **self = self.sub(rhs);
+        self.linear(linear::OpArgs{a: &one, b: &inv})
     }
 }
 impl Mul<&[f64]> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn mul(self, rhs: &[f64]) -> Self::Output {
         let zero: Vec<f64> = rhs.iter().map(|_| 0f64).collect();
-        self.linear(linear::OpArgs{a: rhs, b: &zero}).unwrap_or_default()
-    }
-}
-impl MulAssign<&[f64]> for &mut VipsImage {
-    fn mul_assign(&mut self, rhs: &[f64]){
-        **self = self.mul(rhs);
+        self.linear(linear::OpArgs{a: rhs, b: &zero})
     }
 }
 impl Div<&[f64]> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn div(self, rhs: &[f64]) -> Self::Output {
         let zero: Vec<f64> = rhs.iter().map(|_| 0f64).collect();
         let inv: Vec<f64> = rhs.iter().map(|x| 1f64/x).collect();
-        self.linear(linear::OpArgs{a: &inv, b: &zero}).unwrap_or_default()
-    }
-}
-impl DivAssign<&[f64]> for &mut VipsImage {
-    fn div_assign(&mut self, rhs: &[f64]){
-        **self = self.div(rhs);
+        self.linear(linear::OpArgs{a: &inv, b: &zero})
     }
 }
 
-// TODO: resolve naming conflict
-// impl Add<f64> for &VipsImage {
-//     type Output = VipsImage;
-//     fn add(self, rhs: f64) -> Self::Output {
-//         self.linear(linear::OpArgs{a: 1, b: &rhs}).unwrap_or_default()
-//     }
-// }
-impl AddAssign<f64> for &mut VipsImage {
-    fn add_assign(&mut self, rhs: f64) {
-        **self = self.linear1(linear1::OpArgs{a: 1f64, b: rhs}).unwrap_or_default();
+impl Add<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn add(self, rhs: f64) -> Self::Output {
+        self.linear1(linear1::OpArgs{a: 1f64, b: rhs})
     }
 }
 impl Sub<f64> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn sub(self, rhs: f64) -> Self::Output {
-        self.linear1(linear1::OpArgs{a: 1f64, b: -rhs}).unwrap_or_default()
-    }
-}
-impl SubAssign<f64> for &mut VipsImage {
-    fn sub_assign(&mut self, rhs: f64){
-        **self = self.sub(rhs);
+        self.linear1(linear1::OpArgs{a: 1f64, b: -rhs})
     }
 }
 impl Mul<f64> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn mul(self, rhs: f64) -> Self::Output {
-        self.linear1(linear1::OpArgs{a: rhs, b: 0f64}).unwrap_or_default()
-    }
-}
-impl MulAssign<f64> for &mut VipsImage {
-    fn mul_assign(&mut self, rhs: f64){
-        **self = self.mul(rhs);
+        self.linear1(linear1::OpArgs{a: rhs, b: 0f64})
     }
 }
 impl Div<f64> for &VipsImage {
-    type Output = VipsImage;
+    type Output = Result<VipsImage, VipsError>;
     fn div(self, rhs: f64) -> Self::Output {
-        self.linear1(linear1::OpArgs{a: 1f64/rhs, b: 0f64}).unwrap_or_default()
+        self.linear1(linear1::OpArgs{a: 1f64/rhs, b: 0f64})
+    }
+}
+
+impl Rem<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn rem(self, rhs: &VipsImage) -> Self::Output {
+        self.remainder(remainder::OpArgs{right: rhs})
+    }
+}
+impl Rem<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn rem(self, rhs: &[f64]) -> Self::Output {
+        self.remainder_const(remainder_const::OpArgs{c: rhs})
+    }
+}
+impl Rem<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn rem(self, rhs: f64) -> Self::Output {
+        self.remainder_const1(rhs)
+    }
+}
+
+impl BitAnd<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitand(self, rhs: &VipsImage) -> Self::Output {
+        self.andimage(rhs)
+    }
+}
+impl BitAnd<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitand(self, rhs: &[f64]) -> Self::Output {
+        self.andimage_const(rhs)
+    }
+}
+impl BitAnd<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitand(self, rhs: f64) -> Self::Output {
+        self.andimage_const1(rhs)
+    }
+}
+
+impl BitOr<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitor(self, rhs: &VipsImage) -> Self::Output {
+        self.orimage(rhs)
+    }
+}
+impl BitOr<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitor(self, rhs: &[f64]) -> Self::Output {
+        self.orimage_const(rhs)
+    }
+}
+impl BitOr<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitor(self, rhs: f64) -> Self::Output {
+        self.orimage_const1(rhs)
+    }
+}
+
+impl BitXor<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitxor(self, rhs: &VipsImage) -> Self::Output {
+        self.eorimage(rhs)
     }
 }
-impl DivAssign<f64> for &mut VipsImage {
-    fn div_assign(&mut self, rhs: f64){
-        **self = self.div(rhs);
+impl BitXor<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitxor(self, rhs: &[f64]) -> Self::Output {
+        self.eorimage_const(rhs)
+    }
+}
+impl BitXor<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn bitxor(self, rhs: f64) -> Self::Output {
+        self.eorimage_const1(rhs)
+    }
+}
+
+impl Shl<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shl(self, rhs: &VipsImage) -> Self::Output {
+        self.lshift(rhs)
+    }
+}
+impl Shl<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shl(self, rhs: &[f64]) -> Self::Output {
+        self.lshift_const(rhs)
+    }
+}
+impl Shl<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shl(self, rhs: f64) -> Self::Output {
+        self.lshift_const1(rhs)
+    }
+}
+
+impl Shr<&VipsImage> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shr(self, rhs: &VipsImage) -> Self::Output {
+        self.rshift(rhs)
+    }
+}
+impl Shr<&[f64]> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shr(self, rhs: &[f64]) -> Self::Output {
+        self.rshift_const(rhs)
+    }
+}
+impl Shr<f64> for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn shr(self, rhs: f64) -> Self::Output {
+        self.rshift_const1(rhs)
+    }
+}
+
+impl Neg for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    fn neg(self) -> Self::Output {
+        self.linear1(linear1::OpArgs{a: -1f64, b: 0f64})
+    }
+}
+impl Not for &VipsImage {
+    type Output = Result<VipsImage, VipsError>;
+    /// Invert all bits of integer images, like `!` on integers. Use `invert`
+    /// to invert the colours of an image.
+    fn not(self) -> Self::Output {
+        self.eorimage_const1(-1f64)
     }
 }
 
@@ -171,7 +249,7 @@ define_operator!(linear, struct Args<'a> {
 
 // linear1 needs to be implemented manually since it's not technically an
 // operation
-mod linear1 {
+pub mod linear1 {
     pub struct OpArgs {
         pub a: f64,
         pub b: f64
@@ -186,109 +264,296 @@ mod linear1 {
         }
     }
 }
-// int vips_remainder()
-// int vips_remainder_const()
-// int vips_remainder_const1()
-// int vips_invert()
-// int vips_abs()
-// int vips_sign()
-// int vips_round()
-// int vips_floor()
-// int vips_ceil()
-// int vips_rint()
-// int vips_math()
-// int vips_sin()
-// int vips_cos()
-// int vips_tan()
-// int vips_asin()
-// int vips_acos()
-// int vips_atan()
-// int vips_exp()
-// int vips_exp10()
-// int vips_log()
-// int vips_log10()
-// int vips_sinh()
-// int vips_cosh()
-// int vips_tanh()
-// int vips_asinh()
-// int vips_acosh()
-// int vips_atanh()
-// int vips_complex()
-// int vips_polar()
-// int vips_rect()
-// int vips_conj()
-// int vips_complex2()
-// int vips_cross_phase()
-// int vips_complexget()
-// int vips_real()
-// int vips_imag()
-// int vips_complexform()
-// int vips_relational()
-// int vips_equal()
-// int vips_notequal()
-// int vips_less()
-// int vips_lesseq()
-// int vips_more()
-// int vips_moreeq()
-// int vips_relational_const()
-// int vips_equal_const()
-// int vips_notequal_const()
-// int vips_less_const()
-// int vips_lesseq_const()
-// int vips_more_const()
-// int vips_moreeq_const()
-// int vips_relational_const1()
-// int vips_equal_const1()
-// int vips_notequal_const1()
-// int vips_less_const1()
-// int vips_lesseq_const1()
-// int vips_more_const1()
-// int vips_moreeq_const1()
-// int vips_boolean()
-// int vips_andimage()
-// int vips_orimage()
-// int vips_eorimage()
-// int vips_lshift()
-// int vips_rshift()
-// int vips_boolean_const()
-// int vips_andimage_const()
-// int vips_orimage_const()
-// int vips_eorimage_const()
-// int vips_lshift_const()
-// int vips_rshift_const()
-// int vips_boolean_const1()
-// int vips_andimage_const1()
-// int vips_orimage_const1()
-// int vips_eorimage_const1()
-// int vips_lshift_const1()
-// int vips_rshift_const1()
-// int vips_math2()
-// int vips_pow()
-// int vips_wop()
-// int vips_atan2()
-// int vips_math2_const()
-// int vips_pow_const()
-// int vips_wop_const()
-// int vips_atan2_const()
-// int vips_math2_const1()
-// int vips_pow_const1()
-// int vips_wop_const1()
-// int vips_atan2_const1()
-// int vips_avg()
-// int vips_deviate()
-// int vips_min()
-// int vips_max()
-// int vips_stats()
-// int vips_measure()
-// int vips_find_trim()
-// int vips_getpoint()
+define_operator!(remainder, "left", struct Args<'a> {
+    pub right: &'a VipsImage
+});
+define_operator!(remainder_const, struct Args<'a> {
+    pub c: &'a [f64]
+});
+define_operator!(invert);
+define_operator!(abs);
+define_operator!(sign);
+define_operator!(round, struct Args {
+    pub round: OperationRound
+});
+define_operator!(math, struct Args {
+    pub math: OperationMath
+});
+define_operator!(math2, "left", struct Args<'a> {
+    pub right: &'a VipsImage,
+    pub math2: OperationMath2
+});
+define_operator!(math2_const, struct Args<'a> {
+    pub math2: OperationMath2,
+    pub c: &'a [f64]
+});
+define_operator!(complex, struct Args {
+    pub cmplx: OperationComplex
+});
+define_operator!(complex2, "left", struct Args<'a> {
+    pub right: &'a VipsImage,
+    pub cmplx: OperationComplex2
+});
+define_operator!(complexget, struct Args {
+    pub get: OperationComplexget
+});
+define_operator!(complexform, "left", struct Args<'a> {
+    pub right: &'a VipsImage
+});
+define_operator!(relational, "left", struct Args<'a> {
+    pub right: &'a VipsImage,
+    pub relational: OperationRelational
+});
+define_operator!(relational_const, struct Args<'a> {
+    pub relational: OperationRelational,
+    pub c: &'a [f64]
+});
+define_operator!(boolean, "left", struct Args<'a> {
+    pub right: &'a VipsImage,
+    pub boolean: OperationBoolean
+});
+define_operator!(boolean_const, struct Args<'a> {
+    pub boolean: OperationBoolean,
+    pub c: &'a [f64]
+});
+
+// Shorthands mirroring the convenience functions of the C API, e.g.
+// `vips_floor()` for `vips_round()` with `VIPS_OPERATION_ROUND_FLOOR`
+impl VipsImage {
+    /// Remainder after dividing each band by the constant `c`
+    pub fn remainder_const1(&self, c: f64) -> Result<VipsImage, VipsError> {
+        self.remainder_const(remainder_const::OpArgs{c: &[c]})
+    }
+    /// Compare each band with the constant `c`
+    pub fn relational_const1(
+        &self,
+        relational: OperationRelational,
+        c: f64
+    ) -> Result<VipsImage, VipsError> {
+        self.relational_const(relational_const::OpArgs{relational, c: &[c]})
+    }
+    /// Bitwise operation on each band and the constant `c`
+    pub fn boolean_const1(&self, boolean: OperationBoolean, c: f64) -> Result<VipsImage, VipsError> {
+        self.boolean_const(boolean_const::OpArgs{boolean, c: &[c]})
+    }
+    /// Binary maths on each band and the constant `c`
+    pub fn math2_const1(&self, math2: OperationMath2, c: f64) -> Result<VipsImage, VipsError> {
+        self.math2_const(math2_const::OpArgs{math2, c: &[c]})
+    }
+
+    // === Rounding ============================================================
+
+    /// Round to the nearest integer
+    pub fn rint(&self) -> Result<VipsImage, VipsError> {
+        self.round(round::OpArgs{round: OperationRound::Rint})
+    }
+    /// Round down to the next integer
+    pub fn floor(&self) -> Result<VipsImage, VipsError> {
+        self.round(round::OpArgs{round: OperationRound::Floor})
+    }
+    /// Round up to the next integer
+    pub fn ceil(&self) -> Result<VipsImage, VipsError> {
+        self.round(round::OpArgs{round: OperationRound::Ceil})
+    }
+
+    // === Unary maths =========================================================
+
+    /// Sine of each pixel, in degrees
+    pub fn sin(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Sin})
+    }
+    /// Cosine of each pixel, in degrees
+    pub fn cos(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Cos})
+    }
+    /// Tangent of each pixel, in degrees
+    pub fn tan(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Tan})
+    }
+    /// Arc sine of each pixel, in degrees
+    pub fn asin(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Asin})
+    }
+    /// Arc cosine of each pixel, in degrees
+    pub fn acos(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Acos})
+    }
+    /// Arc tangent of each pixel, in degrees
+    pub fn atan(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Atan})
+    }
+    /// e to the power of each pixel
+    pub fn exp(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Exp})
+    }
+    /// 10 to the power of each pixel
+    pub fn exp10(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Exp10})
+    }
+    /// Natural logarithm of each pixel
+    pub fn log(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Log})
+    }
+    /// Base 10 logarithm of each pixel
+    pub fn log10(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Log10})
+    }
+    /// Hyperbolic sine of each pixel
+    pub fn sinh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Sinh})
+    }
+    /// Hyperbolic cosine of each pixel
+    pub fn cosh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Cosh})
+    }
+    /// Hyperbolic tangent of each pixel
+    pub fn tanh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Tanh})
+    }
+    /// Inverse hyperbolic sine of each pixel
+    pub fn asinh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Asinh})
+    }
+    /// Inverse hyperbolic cosine of each pixel
+    pub fn acosh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Acosh})
+    }
+    /// Inverse hyperbolic tangent of each pixel
+    pub fn atanh(&self) -> Result<VipsImage, VipsError> {
+        self.math(math::OpArgs{math: OperationMath::Atanh})
+    }
+
+    // === Binary maths ========================================================
+
+    /// `self` to the power of `right`
+    pub fn pow(&self, right: &VipsImage) -> Result<VipsImage, VipsError> {
+        self.math2(math2::OpArgs{right, math2: OperationMath2::Pow})
+    }
+    /// `right` to the power of `self`
+    pub fn wop(&self, right: &VipsImage) -> Result<VipsImage, VipsError> {
+        self.math2(math2::OpArgs{right, math2: OperationMath2::Wop})
+    }
+    /// Arc tangent of `self / right`, in degrees
+    pub fn atan2(&self, right: &VipsImage) -> Result<VipsImage, VipsError> {
+        self.math2(math2::OpArgs{right, math2: OperationMath2::Atan2})
+    }
+    /// Each band to the power of the matching element of `c`
+    pub fn pow_const(&self, c: &[f64]) -> Result<VipsImage, VipsError> {
+        self.math2_const(math2_const::OpArgs{math2: OperationMath2::Pow, c})
+    }
+    /// The matching element of `c` to the power of each band
+    pub fn wop_const(&self, c: &[f64]) -> Result<VipsImage, VipsError> {
+        self.math2_const(math2_const::OpArgs{math2: OperationMath2::Wop, c})
+    }
+    /// Arc tangent of each band divided by the matching element of `c`
+    pub fn atan2_const(&self, c: &[f64]) -> Result<VipsImage, VipsError> {
+        self.math2_const(math2_const::OpArgs{math2: OperationMath2::Atan2, c})
+    }
+    /// Each pixel to the power of `c`
+    pub fn pow_const1(&self, c: f64) -> Result<VipsImage, VipsError> {
+        self.pow_const(&[c])
+    }
+    /// `c` to the power of each pixel
+    pub fn wop_const1(&self, c: f64) -> Result<VipsImage, VipsError> {
+        self.wop_const(&[c])
+    }
+    /// Arc tangent of each pixel divided by `c`
+    pub fn atan2_const1(&self, c: f64) -> Result<VipsImage, VipsError> {
+        self.atan2_const(&[c])
+    }
+
+    // === Complex =============================================================
+
+    /// Convert complex pixels to polar coordinates
+    pub fn polar(&self) -> Result<VipsImage, VipsError> {
+        self.complex(complex::OpArgs{cmplx: OperationComplex::Polar})
+    }
+    /// Convert complex pixels to rectangular coordinates
+    pub fn rect(&self) -> Result<VipsImage, VipsError> {
+        self.complex(complex::OpArgs{cmplx: OperationComplex::Rect})
+    }
+    /// Complex conjugate of each pixel
+    pub fn conj(&self) -> Result<VipsImage, VipsError> {
+        self.complex(complex::OpArgs{cmplx: OperationComplex::Conj})
+    }
+    /// Cross phase of `self` and `right`
+    pub fn cross_phase(&self, right: &VipsImage) -> Result<VipsImage, VipsError> {
+        self.complex2(complex2::OpArgs{right, cmplx: OperationComplex2::CrossPhase})
+    }
+    /// Real part of each complex pixel
+    pub fn real(&self) -> Result<VipsImage, VipsError> {
+        self.complexget(complexget::OpArgs{get: OperationComplexget::Real})
+    }
+    /// Imaginary part of each complex pixel
+    pub fn imag(&self) -> Result<VipsImage, VipsError> {
+        self.complexget(complexget::OpArgs{get: OperationComplexget::Imag})
+    }
+}
+
+// Relational and boolean operators, with `_const` variants taking one constant
+// per band and `_const1` variants taking a single constant for all bands.
+macro_rules! define_shorthands {
+    (
+        $op:ident, $op_const:ident, $enum:ident, $field:ident;
+        $(
+            $(#[$meta:meta])*
+            $name:ident, $name_const:ident, $name_const1:ident => $variant:ident
+        );* $(;)?
+    ) => {
+        impl VipsImage {
+            $(
+                $(#[$meta])*
+                pub fn $name(&self, right: &VipsImage) -> Result<VipsImage, VipsError> {
+                    self.$op($op::OpArgs{right, $field: $enum::$variant})
+                }
+                $(#[$meta])*
+                pub fn $name_const(&self, c: &[f64]) -> Result<VipsImage, VipsError> {
+                    self.$op_const($op_const::OpArgs{$field: $enum::$variant, c})
+                }
+                $(#[$meta])*
+                pub fn $name_const1(&self, c: f64) -> Result<VipsImage, VipsError> {
+                    self.$name_const(&[c])
+                }
+            )*
+        }
+    };
+}
+
+define_shorthands!(
+    relational, relational_const, OperationRelational, relational;
+    /// 255 where `self == right`, 0 elsewhere
+    equal, equal_const, equal_const1 => Equal;
+    /// 255 where `self != right`, 0 elsewhere
+    notequal, notequal_const, notequal_const1 => NotEq;
+    /// 255 where `self < right`, 0 elsewhere
+    less, less_const, less_const1 => Less;
+    /// 255 where `self <= right`, 0 elsewhere
+    lesseq, lesseq_const, lesseq_const1 => LessEq;
+    /// 255 where `self > right`, 0 elsewhere
+    more, more_const, more_const1 => More;
+    /// 255 where `self >= right`, 0 elsewhere
+    moreeq, moreeq_const, moreeq_const1 => MoreEq
+);
+
+define_shorthands!(
+    boolean, boolean_const, OperationBoolean, boolean;
+    /// Bitwise and
+    andimage, andimage_const, andimage_const1 => And;
+    /// Bitwise or
+    orimage, orimage_const, orimage_const1 => Or;
+    /// Bitwise exclusive or
+    eorimage, eorimage_const, eorimage_const1 => Eor;
+    /// Shift bits left
+    lshift, lshift_const, lshift_const1 => LShift;
+    /// Shift bits right
+    rshift, rshift_const, rshift_const1 => RShift
+);
+
 // int vips_hist_find()
 // int vips_hist_find_ndim()
 // int vips_hist_find_indexed()
 // int vips_hough_line()
 // int vips_hough_circle()
-// int vips_project()
-// int vips_profile()
 
 #[cfg(test)]
 mod tests {
@@ -360,11 +625,82 @@ mod tests {
             .expect("Image could not be created from file");
         assert_ne!(img.ptr, std::ptr::null_mut());
 
-        let _transformed = &(&img - 30.) / vec![0.9, 1.1, 1.].as_slice();
+        let _transformed = (&(&img - 30.).expect("Could not subtract constant")
+            / vec![0.9, 1.1, 1.].as_slice())
+            .expect("Could not divide by constants");
 
         // TODO: check if pixel values make sense
 
         // _transformed.write_to_file(PathBuf::from("./data/test_linear.jpg"))
         //     .expect("Could not write result to file");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn operators() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let sum = (&img + &img).expect("Could not add images");
+        assert_eq!(sum.width(), img.width());
+        let _scaled = (&sum * 0.5).expect("Could not multiply by constant");
+
+        // test.jpg has three bands, so two constants can not be applied
+        match &img + [1., 2.].as_slice() {
+            Ok(_) => panic!("Adding a mismatched vector should error"),
+            Err(e) => assert_eq!(e.operation(), Some("linear"))
+        }
+    }
+
+    #[test]
+    fn math() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_matrix_from_array(4, 1, &[-1.5, 0.5, 2.25, 7.])
+            .expect("Could not create matrix");
+
+        let mut floored = img.floor().expect("Could not floor image");
+        assert_eq!(floored.as_slice::<f64>().expect("Could not borrow image data"), &[-2., 0., 2., 7.]);
+
+        let mut absolute = img.abs().expect("Could not get absolute value");
+        assert_eq!(absolute.as_slice::<f64>().expect("Could not borrow image data"), &[1.5, 0.5, 2.25, 7.]);
+
+        let mut squared = img.pow_const1(2.).expect("Could not raise image to power");
+        assert_eq!(squared.as_slice::<f64>().expect("Could not borrow image data"), &[2.25, 0.25, 5.0625, 49.]);
+
+        let mut negated = (-&img).expect("Could not negate image");
+        assert_eq!(negated.as_slice::<f64>().expect("Could not borrow image data"), &[1.5, -0.5, -2.25, -7.]);
+
+        let _sine = img.sin().expect("Could not get sine");
+    }
+
+    #[test]
+    fn relational_boolean() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_matrix_from_array(4, 1, &[1., 2., 3., 4.])
+            .expect("Could not create matrix");
+
+        let mut more = img.more_const1(2.).expect("Could not compare image");
+        assert_eq!(more.as_slice::<u8>().expect("Could not borrow image data"), &[0, 0, 255, 255]);
+
+        let mut less = img.relational_const1(OperationRelational::Less, 3.)
+            .expect("Could not compare image");
+        assert_eq!(less.as_slice::<u8>().expect("Could not borrow image data"), &[255, 255, 0, 0]);
+
+        let odd = img.boolean_const1(OperationBoolean::And, 1.).expect("Could not and image");
+        assert_eq!(odd.avg().expect("Could not average image"), 0.5);
+
+        let mut squared = img.math2_const1(OperationMath2::Pow, 2.).expect("Could not raise image to power");
+        assert_eq!(squared.as_slice::<f64>().expect("Could not borrow image data"), &[1., 4., 9., 16.]);
+
+        let mut remainder = (&img % 3.).expect("Could not get remainder");
+        assert_eq!(remainder.as_slice::<f64>().expect("Could not borrow image data"), &[1., 2., 0., 1.]);
+
+        let _masked = (&img & &img).expect("Could not and images");
+        let _shifted = (&img << 1.).expect("Could not shift image");
+        let _flipped = (!&img).expect("Could not invert bits");
+    }
+}
@@ -1,10 +1,17 @@
 // most colour space conversion function names become unreadable in snake case
 #![allow(non_snake_case)]
 
+use std::ffi::{CString, c_char, c_void};
+
 use vips_sys as s;
 
 use crate::{
     VipsImage,
+    VipsError,
+    MetaValue,
+    Intent,
+    Interpretation,
+    Pcs,
     define_operator
 };
 
@@ -17,8 +24,8 @@ impl VipsImage {
 }
 
 define_operator!(colourspace, pub struct ColourSpaceArgs {
-    pub space: s::VipsInterpretation,
-    pub source_space: Option<s::VipsInterpretation>
+    pub space: Interpretation,
+    pub source_space: Option<Interpretation>
 });
 
 define_operator!(LabQ2sRGB);
@@ -50,47 +57,348 @@ define_operator!(Lab2LabS);
 define_operator!(CMYK2XYZ);
 define_operator!(XYZ2CMYK);
 
-// TODO: int      vips_profile_load()
-// TODO: int      vips_icc_present()
-// TODO: int      vips_icc_transform()
-// TODO: int      vips_icc_import()
-// TODO: int      vips_icc_export()
-// TODO: int      vips_icc_ac2rc()
-// TODO: gboolean vips_icc_is_compatible_profile()
-// TODO: int      vips_dE76()
-// TODO: int      vips_dE00()
-// TODO: int      vips_dECMC()
-// TODO: void     vips_col_Lab2XYZ()
-// TODO: void     vips_col_XYZ2Lab()
-// TODO: double   vips_col_ab2h()
-// TODO: void     vips_col_ab2Ch()
-// TODO: void     vips_col_Ch2ab()
-// TODO: float    vips_col_L2Lcmc()
-// TODO: float    vips_col_C2Ccmc()
-// TODO: float    vips_col_Ch2hcmc()
-// TODO: void     vips_col_make_tables_CMC()
-// TODO: float    vips_col_Lcmc2L()
-// TODO: float    vips_col_Ccmc2C()
-// TODO: float    vips_col_Chcmc2h()
-// TODO: int      vips_col_sRGB2scRGB_8()
-// TODO: int      vips_col_sRGB2scRGB_16()
-// TODO: int      vips_col_sRGB2scRGB_8_noclip()
-// TODO: int      vips_col_sRGB2scRGB_16_noclip()
-// TODO: int      vips_col_scRGB2XYZ()
-// TODO: int      vips_col_XYZ2scRGB()
-// TODO: int      vips_col_scRGB2sRGB_8()
-// TODO: int      vips_col_scRGB2sRGB_16()
-// TODO: int      vips_col_scRGB2BW_16()
-// TODO: int      vips_col_scRGB2BW_8()
-// TODO: float    vips_pythagoras()
-// TODO: float    vips_col_dE00()
+// === ICC =====================================================================
+
+/// Whether Vips was built with ICC support. The `icc_*` operators fail
+/// without it.
+pub fn icc_present() -> bool {
+    unsafe {
+        s::vips_icc_present() != 0
+    }
+}
+
+/// Load an ICC profile. `name` is a file name or one of the profiles built
+/// into Vips: `"srgb"`, `"cmyk"` or `"p3"`. `"none"` loads no profile and
+/// returns an empty `Vec`.
+pub fn profile_load(name: &str) -> Result<Vec<u8>, VipsError> {
+    let name_c_str = CString::new(name)?;
+
+    unsafe {
+        let mut blob: *mut s::VipsBlob = std::ptr::null_mut();
+        if s::vips_profile_load(
+            name_c_str.as_ptr(),
+            &mut blob,
+            std::ptr::null::<c_char>()
+        ) != 0 {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        // "none" gives no blob at all
+        if blob.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let mut length: usize = 0;
+        let data = s::vips_blob_get(blob, &mut length);
+        let profile = std::slice::from_raw_parts(data as *const u8, length).to_vec();
+        s::vips_area_unref(blob as *mut s::VipsArea);
+
+        Ok(profile)
+    }
+}
+
+impl VipsImage {
+    /// The ICC profile embedded in the image, if it has one
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        if !self.has_field("icc-profile-data") {
+            return None;
+        }
+
+        match self.get("icc-profile-data") {
+            Ok(MetaValue::Blob(profile)) => Some(profile),
+            _ => None
+        }
+    }
+
+    /// Embed `profile` in the image, replacing any previous profile. Savers
+    /// write the embedded profile to the file.
+    pub fn set_icc_profile(&mut self, profile: &[u8]) -> Result<(), VipsError> {
+        self.set("icc-profile-data", MetaValue::Blob(profile.to_vec()))
+    }
+
+    /// Remove the embedded ICC profile. Returns `false` if the image has no
+    /// profile.
+    pub fn remove_icc_profile(&mut self) -> bool {
+        self.remove("icc-profile-data")
+    }
+
+    /// Whether `profile` can be used with the image, i.e. matches its number
+    /// of bands and colour space
+    pub fn icc_is_compatible_profile(&self, profile: &[u8]) -> bool {
+        unsafe {
+            s::vips_icc_is_compatible_profile(
+                self.ptr,
+                profile.as_ptr() as *const c_void,
+                profile.len()
+            ) != 0
+        }
+    }
+
+    /// Convert a Lab image from absolute to relative colorimetry, using the
+    /// white point of the profile at `profile_filename`
+    pub fn icc_ac2rc(&self, profile_filename: &str) -> Result<VipsImage, VipsError> {
+        let profile_filename_c_str = CString::new(profile_filename)?;
+
+        unsafe {
+            let mut out: *mut s::VipsImage = std::ptr::null_mut();
+            if s::vips_icc_ac2rc(self.ptr, &mut out, profile_filename_c_str.as_ptr()) != 0 {
+                return Err(VipsError::new_from_vips_state());
+            }
+            VipsImage::from_c_ptr(out)
+        }
+    }
+}
+
+define_operator!(icc_import,
+    /// Import to the profile connection space (Lab or XYZ, see `pcs`) using
+    /// the embedded profile or `input_profile`
+    struct Args<'a> {
+        /// Profile connection space to import to, Lab by default
+        pub pcs: Option<Pcs>,
+        /// Rendering intent
+        pub intent: Option<Intent>,
+        /// Enable black point compensation
+        pub black_point_compensation: Option<bool>,
+        /// Use the embedded profile if the image has one
+        pub embedded: Option<bool>,
+        /// Filename of the profile to use if there is no embedded one
+        pub input_profile: Option<&'a str>
+    }
+);
+
+define_operator!(icc_export,
+    /// Export from the profile connection space to device space using
+    /// `output_profile`
+    struct Args<'a> {
+        /// Profile connection space to export from, Lab by default
+        pub pcs: Option<Pcs>,
+        /// Rendering intent
+        pub intent: Option<Intent>,
+        /// Enable black point compensation
+        pub black_point_compensation: Option<bool>,
+        /// Filename of the device profile to export to
+        pub output_profile: Option<&'a str>,
+        /// Bits per band of the output, 8 or 16
+        pub depth: Option<i32>
+    }
+);
+
+define_operator!(icc_transform,
+    /// Transform from the embedded profile or `input_profile` to
+    /// `output_profile`
+    struct Args<'a> {
+        /// Filename of the device profile to transform to
+        pub output_profile: &'a str,
+        /// Profile connection space to go through, Lab by default
+        pub pcs: Option<Pcs>,
+        /// Rendering intent
+        pub intent: Option<Intent>,
+        /// Enable black point compensation
+        pub black_point_compensation: Option<bool>,
+        /// Use the embedded profile if the image has one
+        pub embedded: Option<bool>,
+        /// Filename of the profile to use if there is no embedded one
+        pub input_profile: Option<&'a str>,
+        /// Bits per band of the output, 8 or 16
+        pub depth: Option<i32>
+    }
+);
+
+// === Colour difference =======================================================
+
+define_operator!(dE76, "left",
+    /// CIE76 colour difference between `self` and `right`, i.e. the Euclidean
+    /// distance in Lab space. Returns a 1-band float image.
+    struct Args<'a> {
+        /// The Lab image to compare with
+        pub right: &'a VipsImage
+    }
+);
+
+define_operator!(dE00, "left",
+    /// CIEDE2000 colour difference between `self` and `right`. Returns a
+    /// 1-band float image.
+    struct Args<'a> {
+        /// The Lab image to compare with
+        pub right: &'a VipsImage
+    }
+);
+
+define_operator!(dECMC, "left",
+    /// CMC(l:c) colour difference between `self` and `right`. Returns a
+    /// 1-band float image.
+    struct Args<'a> {
+        /// The Lab image to compare with
+        pub right: &'a VipsImage
+    }
+);
+
+// === Single colours ==========================================================
+
+/// Conversions between colour spaces for single colours, using the same
+/// formulas as the image operators
+///
+/// Colours are passed as tuples of their components, e.g. `(L, a, b)`. Lab
+/// uses the D65 white point, XYZ is scaled so Y is 0 - 100 and scRGB is linear
+/// light in the range 0 - 1.
+pub mod scalar {
+    use std::ffi::c_int;
+
+    use vips_sys as s;
+
+    pub fn Lab2XYZ((l, a, b): (f32, f32, f32)) -> (f32, f32, f32) {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_Lab2XYZ(l, a, b, &mut x, &mut y, &mut z) };
+        (x, y, z)
+    }
+
+    pub fn XYZ2Lab((x, y, z): (f32, f32, f32)) -> (f32, f32, f32) {
+        let (mut l, mut a, mut b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_XYZ2Lab(x, y, z, &mut l, &mut a, &mut b) };
+        (l, a, b)
+    }
+
+    /// Hue angle in degrees of the chroma components `a` and `b`
+    pub fn ab2h(a: f64, b: f64) -> f64 {
+        unsafe { s::vips_col_ab2h(a, b) }
+    }
+
+    /// Chroma and hue angle in degrees of the chroma components `a` and `b`
+    pub fn ab2Ch((a, b): (f32, f32)) -> (f32, f32) {
+        let (mut c, mut h) = (0.0, 0.0);
+        unsafe { s::vips_col_ab2Ch(a, b, &mut c, &mut h) };
+        (c, h)
+    }
+
+    /// Chroma components `a` and `b` of chroma `c` and hue angle `h` in degrees
+    pub fn Ch2ab((c, h): (f32, f32)) -> (f32, f32) {
+        let (mut a, mut b) = (0.0, 0.0);
+        unsafe { s::vips_col_Ch2ab(c, h, &mut a, &mut b) };
+        (a, b)
+    }
+
+    pub fn L2Lcmc(l: f32) -> f32 {
+        unsafe { s::vips_col_L2Lcmc(l) }
+    }
+
+    pub fn C2Ccmc(c: f32) -> f32 {
+        unsafe { s::vips_col_C2Ccmc(c) }
+    }
+
+    pub fn Ch2hcmc((c, h): (f32, f32)) -> f32 {
+        unsafe { s::vips_col_Ch2hcmc(c, h) }
+    }
+
+    // the inverse CMC conversions look up tables which are built on first use
+
+    pub fn Lcmc2L(lcmc: f32) -> f32 {
+        unsafe {
+            s::vips_col_make_tables_CMC();
+            s::vips_col_Lcmc2L(lcmc)
+        }
+    }
+
+    pub fn Ccmc2C(ccmc: f32) -> f32 {
+        unsafe {
+            s::vips_col_make_tables_CMC();
+            s::vips_col_Ccmc2C(ccmc)
+        }
+    }
+
+    pub fn Chcmc2h((c, hcmc): (f32, f32)) -> f32 {
+        unsafe {
+            s::vips_col_make_tables_CMC();
+            s::vips_col_Chcmc2h(c, hcmc)
+        }
+    }
+
+    /// Convert 8-bit sRGB to scRGB, clipping to 0 - 255 first
+    pub fn sRGB2scRGB_8((r, g, b): (i32, i32, i32)) -> (f32, f32, f32) {
+        let (mut out_r, mut out_g, mut out_b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_sRGB2scRGB_8(r, g, b, &mut out_r, &mut out_g, &mut out_b) };
+        (out_r, out_g, out_b)
+    }
+
+    /// Convert 16-bit sRGB to scRGB, clipping to 0 - 65535 first
+    pub fn sRGB2scRGB_16((r, g, b): (i32, i32, i32)) -> (f32, f32, f32) {
+        let (mut out_r, mut out_g, mut out_b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_sRGB2scRGB_16(r, g, b, &mut out_r, &mut out_g, &mut out_b) };
+        (out_r, out_g, out_b)
+    }
+
+    /// Convert 8-bit sRGB to scRGB without clipping
+    pub fn sRGB2scRGB_8_noclip((r, g, b): (i32, i32, i32)) -> (f32, f32, f32) {
+        let (mut out_r, mut out_g, mut out_b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_sRGB2scRGB_8_noclip(r, g, b, &mut out_r, &mut out_g, &mut out_b) };
+        (out_r, out_g, out_b)
+    }
+
+    /// Convert 16-bit sRGB to scRGB without clipping
+    pub fn sRGB2scRGB_16_noclip((r, g, b): (i32, i32, i32)) -> (f32, f32, f32) {
+        let (mut out_r, mut out_g, mut out_b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_sRGB2scRGB_16_noclip(r, g, b, &mut out_r, &mut out_g, &mut out_b) };
+        (out_r, out_g, out_b)
+    }
+
+    pub fn scRGB2XYZ((r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_scRGB2XYZ(r, g, b, &mut x, &mut y, &mut z) };
+        (x, y, z)
+    }
+
+    pub fn XYZ2scRGB((x, y, z): (f32, f32, f32)) -> (f32, f32, f32) {
+        let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+        unsafe { s::vips_col_XYZ2scRGB(x, y, z, &mut r, &mut g, &mut b) };
+        (r, g, b)
+    }
+
+    /// Convert scRGB to 8-bit sRGB. The flag is set if the colour was out of
+    /// gamut and had to be clipped.
+    pub fn scRGB2sRGB_8((r, g, b): (f32, f32, f32)) -> ((i32, i32, i32), bool) {
+        let (mut out_r, mut out_g, mut out_b, mut out_of_gamut): (c_int, c_int, c_int, c_int) = (0, 0, 0, 0);
+        unsafe { s::vips_col_scRGB2sRGB_8(r, g, b, &mut out_r, &mut out_g, &mut out_b, &mut out_of_gamut) };
+        ((out_r, out_g, out_b), out_of_gamut != 0)
+    }
+
+    /// Convert scRGB to 16-bit sRGB, see `scRGB2sRGB_8`
+    pub fn scRGB2sRGB_16((r, g, b): (f32, f32, f32)) -> ((i32, i32, i32), bool) {
+        let (mut out_r, mut out_g, mut out_b, mut out_of_gamut): (c_int, c_int, c_int, c_int) = (0, 0, 0, 0);
+        unsafe { s::vips_col_scRGB2sRGB_16(r, g, b, &mut out_r, &mut out_g, &mut out_b, &mut out_of_gamut) };
+        ((out_r, out_g, out_b), out_of_gamut != 0)
+    }
+
+    /// Convert scRGB to 8-bit greyscale, see `scRGB2sRGB_8`
+    pub fn scRGB2BW_8((r, g, b): (f32, f32, f32)) -> (i32, bool) {
+        let (mut grey, mut out_of_gamut): (c_int, c_int) = (0, 0);
+        unsafe { s::vips_col_scRGB2BW_8(r, g, b, &mut grey, &mut out_of_gamut) };
+        (grey, out_of_gamut != 0)
+    }
+
+    /// Convert scRGB to 16-bit greyscale, see `scRGB2sRGB_8`
+    pub fn scRGB2BW_16((r, g, b): (f32, f32, f32)) -> (i32, bool) {
+        let (mut grey, mut out_of_gamut): (c_int, c_int) = (0, 0);
+        unsafe { s::vips_col_scRGB2BW_16(r, g, b, &mut grey, &mut out_of_gamut) };
+        (grey, out_of_gamut != 0)
+    }
+
+    /// CIE76 colour difference, i.e. the Euclidean distance between two Lab
+    /// colours
+    pub fn dE76((l1, a1, b1): (f32, f32, f32), (l2, a2, b2): (f32, f32, f32)) -> f32 {
+        unsafe { s::vips_pythagoras(l1, a1, b1, l2, a2, b2) }
+    }
+
+    /// CIEDE2000 colour difference between two Lab colours
+    pub fn dE00((l1, a1, b1): (f32, f32, f32), (l2, a2, b2): (f32, f32, f32)) -> f32 {
+        unsafe { s::vips_col_dE00(l1, a1, b1, l2, a2, b2) }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
     use vips_sys as s;
     use super::*;
-    use crate::ensure_vips_init_or_exit;
+    use crate::{ensure_vips_init_or_exit, BandFormat};
 
     #[test]
     fn colourspace_issupported() {
@@ -112,7 +420,7 @@ mod tests {
         assert_ne!(img.ptr, std::ptr::null_mut());
 
         let result = img.colourspace(colourspace::OpArgs{
-            space: s::VipsInterpretation_VIPS_INTERPRETATION_RGB16,
+            space: Interpretation::Rgb16,
             source_space: None
         })
         .expect("Could not change colourspace");
@@ -120,4 +428,107 @@ mod tests {
         result.write_to_file(PathBuf::from("./data/test_result.tif"))
             .expect("Could not save image to file");
     }
+
+    #[test]
+    fn icc_profiles() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+        if !icc_present() {
+            return;
+        }
+
+        let srgb = profile_load("srgb").expect("Could not load sRGB profile");
+        assert_eq!(&srgb[36..40], b"acsp");
+        assert!(profile_load("none").expect("Could not load empty profile").is_empty());
+        match profile_load("./data/no-such-profile.icc") {
+            Ok(_) => panic!("Loading a missing profile should error"),
+            Err(_) => {}
+        }
+
+        let mut img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        assert!(img.icc_is_compatible_profile(&srgb));
+        img.set_icc_profile(&srgb).expect("Could not set ICC profile");
+        assert_eq!(img.icc_profile(), Some(srgb.clone()));
+
+        let cmyk = img.icc_transform(icc_transform::OpArgs {
+            output_profile: "cmyk",
+            pcs: None,
+            intent: Some(Intent::Perceptual),
+            black_point_compensation: None,
+            embedded: Some(true),
+            input_profile: None,
+            depth: None
+        }).expect("Could not transform to CMYK");
+        assert_eq!(cmyk.nbands(), 4);
+        assert_eq!(cmyk.interpretation(), Interpretation::Cmyk);
+        assert!(cmyk.icc_profile().is_some_and(|profile| profile != srgb));
+
+        let lab = cmyk.icc_import(icc_import::OpArgs {
+            pcs: Some(Pcs::Lab),
+            intent: None,
+            black_point_compensation: None,
+            embedded: Some(true),
+            input_profile: None
+        }).expect("Could not import from CMYK");
+        assert_eq!(lab.interpretation(), Interpretation::Lab);
+
+        let rgb = lab.icc_export(icc_export::OpArgs {
+            pcs: None,
+            intent: None,
+            black_point_compensation: None,
+            output_profile: Some("srgb"),
+            depth: Some(8)
+        }).expect("Could not export to sRGB");
+        assert_eq!(rgb.nbands(), 3);
+        assert_eq!(rgb.format(), BandFormat::UChar);
+
+        assert!(img.remove_icc_profile());
+        assert_eq!(img.icc_profile(), None);
+    }
+
+    #[test]
+    fn colour_difference() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        for difference in [
+            img.dE76(dE76::OpArgs { right: &img }),
+            img.dE00(dE00::OpArgs { right: &img }),
+            img.dECMC(dECMC::OpArgs { right: &img })
+        ] {
+            let mut difference = difference.expect("Could not compute colour difference");
+            assert_eq!(difference.nbands(), 1);
+            assert!(difference.as_slice::<f32>()
+                .expect("Could not borrow difference data")
+                .iter().all(|d| *d == 0.0));
+        }
+    }
+
+    #[test]
+    fn scalar_conversions() {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+        let lab = (50.0, 20.0, -30.0);
+        let roundtrip = scalar::XYZ2Lab(scalar::Lab2XYZ(lab));
+        assert!(close(roundtrip.0, lab.0) && close(roundtrip.1, lab.1) && close(roundtrip.2, lab.2));
+
+        let (c, h) = scalar::ab2Ch((0.0, 10.0));
+        assert!(close(c, 10.0) && close(h, 90.0));
+        assert!((scalar::ab2h(0.0, 10.0) - 90.0).abs() < 0.01);
+        let (a, b) = scalar::Ch2ab((c, h));
+        assert!(close(a, 0.0) && close(b, 10.0));
+
+        assert!(close(scalar::Lcmc2L(scalar::L2Lcmc(50.0)), 50.0));
+
+        let white = scalar::sRGB2scRGB_8((255, 255, 255));
+        assert!(close(white.0, 1.0) && close(white.1, 1.0) && close(white.2, 1.0));
+        assert_eq!(scalar::scRGB2sRGB_8(white), ((255, 255, 255), false));
+        assert!(scalar::scRGB2sRGB_8((2.0, 0.0, 0.0)).1);
+
+        assert!(close(scalar::dE76((50.0, 0.0, 0.0), (53.0, 4.0, 0.0)), 5.0));
+        assert_eq!(scalar::dE00(lab, lab), 0.0);
+    }
 }
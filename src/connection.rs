@@ -0,0 +1,514 @@
+use std::ffi::{CString, c_char, c_int, c_void};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use vips_sys as s;
+
+use crate::*;
+use crate::vips::signal_connect;
+use crate::operator::get_property;
+
+// =============================================================================
+// === VipsSource ==============================================================
+// =============================================================================
+
+/// Safe wrapper around the pointer to the internal `VipsSource*`
+///
+/// A source is something images can be loaded from: a file, a descriptor, a
+/// chunk of memory or any Rust type implementing `Read`. Loaders pull data
+/// from the source as they need it, so large images never have to be held in
+/// memory as a whole.
+pub struct VipsSource {
+    pub ptr: *mut vips_sys::VipsSource
+}
+
+// === Traits ==================================================================
+
+impl Clone for VipsSource {
+    fn clone(&self) -> Self {
+        unsafe { s::g_object_ref(self.ptr as *mut c_void) };
+
+        VipsSource{ ptr: self.ptr }
+    }
+}
+
+impl Drop for VipsSource {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { s::g_object_unref(self.ptr as *mut c_void) };
+        }
+    }
+}
+
+// === Custom behaviour ========================================================
+
+impl VipsSource {
+    /// Wrap a `VipsSource` around a pointer to a `vips_sys::VipsSource`. The
+    /// same ownership rules as for `VipsImage::from_c_ptr` apply.
+    pub fn from_c_ptr(p: *mut vips_sys::VipsSource) -> Result<VipsSource, VipsError> {
+        if p.is_null() {
+            return Err(VipsError::new("Cannot wrap nullptr in VipsSource"));
+        }
+        Ok(VipsSource{ptr: p})
+    }
+
+    /// Create a source reading from the file descriptor `descriptor`.
+    ///
+    /// The descriptor is duplicated, so the caller remains responsible for
+    /// closing it.
+    pub fn new_from_descriptor(descriptor: i32) -> Result<VipsSource, VipsError> {
+        let source = unsafe { s::vips_source_new_from_descriptor(descriptor) };
+        if source.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsSource::from_c_ptr(source)
+    }
+
+    pub fn new_from_file(file: PathBuf) -> Result<VipsSource, VipsError> {
+        let path_str = match file.to_str() {
+            Some(pstr) => pstr,
+            None => return Err(VipsError::new("Could not convert path to string"))
+        };
+        let path_c_str = CString::new(path_str)?;
+
+        let source = unsafe { s::vips_source_new_from_file(path_c_str.as_ptr()) };
+        if source.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsSource::from_c_ptr(source)
+    }
+
+    /// Create a source reading from a copy of `data`.
+    pub fn new_from_memory(data: &[u8]) -> Result<VipsSource, VipsError> {
+        unsafe {
+            let blob = s::vips_blob_copy(data.as_ptr() as *const c_void, data.len());
+            if blob.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            // the source takes its own ref to the blob
+            let source = s::vips_source_new_from_blob(blob);
+            s::vips_area_unref(blob as *mut s::VipsArea);
+            if source.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+            VipsSource::from_c_ptr(source)
+        }
+    }
+
+    /// Create a source reading from `reader`, e.g. a socket or an HTTP body.
+    ///
+    /// Since `reader` cannot seek, Vips may have to buffer some of the data
+    /// for formats that need random access. Prefer `new_from_seekable` where
+    /// possible.
+    pub fn new_from_reader<R: Read + Send + 'static>(reader: R) -> Result<VipsSource, VipsError> {
+        VipsSource::new_custom(Box::new(Unseekable(reader)))
+    }
+
+    /// Create a source reading from `reader`, allowing loaders to seek.
+    pub fn new_from_seekable<R: Read + Seek + Send + 'static>(reader: R) -> Result<VipsSource, VipsError> {
+        VipsSource::new_custom(Box::new(reader))
+    }
+
+    fn new_custom(reader: Box<dyn SourceStream>) -> Result<VipsSource, VipsError> {
+        unsafe {
+            let custom = s::vips_source_custom_new();
+            if custom.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+            // wrap it right away so it is unreffed if connecting fails
+            let source = VipsSource::from_c_ptr(custom as *mut s::VipsSource)?;
+
+            // the double box gives us a thin pointer to pass through glib
+            let data = Box::into_raw(Box::new(reader)) as *mut c_void;
+
+            if let Err(e) = signal_connect(
+                custom as *mut c_void,
+                "read",
+                std::mem::transmute::<
+                    unsafe extern "C" fn(*mut s::VipsSourceCustom, *mut c_void, s::gint64, *mut c_void) -> s::gint64,
+                    unsafe extern "C" fn()
+                >(source_read_cb),
+                data,
+                Some(source_stream_destroy_cb)
+            ) {
+                drop(Box::from_raw(data as *mut Box<dyn SourceStream>));
+                return Err(e);
+            }
+
+            // the "read" handler owns `data`, which lives until the source is
+            // finalized, so the "seek" handler can safely borrow it
+            signal_connect(
+                custom as *mut c_void,
+                "seek",
+                std::mem::transmute::<
+                    unsafe extern "C" fn(*mut s::VipsSourceCustom, s::gint64, c_int, *mut c_void) -> s::gint64,
+                    unsafe extern "C" fn()
+                >(source_seek_cb),
+                data,
+                None
+            )?;
+
+            Ok(source)
+        }
+    }
+}
+
+// --- Custom source callbacks ---
+
+trait SourceStream: Send {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize>;
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64>;
+}
+
+impl<R: Read + Seek + Send> SourceStream for R {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Read::read(self, buf)
+    }
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        Seek::seek(self, pos)
+    }
+}
+
+struct Unseekable<R>(R);
+
+impl<R: Read + Send> SourceStream for Unseekable<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+    fn seek(&mut self, _pos: SeekFrom) -> std::io::Result<u64> {
+        Err(std::io::ErrorKind::Unsupported.into())
+    }
+}
+
+unsafe extern "C" fn source_read_cb(
+    _source: *mut s::VipsSourceCustom,
+    buffer: *mut c_void,
+    length: s::gint64,
+    user_data: *mut c_void
+) -> s::gint64 {
+    let stream = &mut *(user_data as *mut Box<dyn SourceStream>);
+    let buf = std::slice::from_raw_parts_mut(buffer as *mut u8, length as usize);
+
+    loop {
+        match stream.read(buf) {
+            Ok(n) => return n as s::gint64,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(_) => return -1
+        }
+    }
+}
+
+unsafe extern "C" fn source_seek_cb(
+    _source: *mut s::VipsSourceCustom,
+    offset: s::gint64,
+    whence: c_int,
+    user_data: *mut c_void
+) -> s::gint64 {
+    let stream = &mut *(user_data as *mut Box<dyn SourceStream>);
+
+    let pos = match whence {
+        0 => SeekFrom::Start(offset as u64),
+        1 => SeekFrom::Current(offset),
+        2 => SeekFrom::End(offset),
+        _ => return -1
+    };
+
+    match stream.seek(pos) {
+        Ok(new_pos) => new_pos as s::gint64,
+        Err(_) => -1
+    }
+}
+
+unsafe extern "C" fn source_stream_destroy_cb(data: *mut c_void, _closure: *mut s::GClosure) {
+    drop(Box::from_raw(data as *mut Box<dyn SourceStream>));
+}
+
+// =============================================================================
+// === VipsTarget ==============================================================
+// =============================================================================
+
+/// Safe wrapper around the pointer to the internal `VipsTarget*`
+///
+/// A target is something images can be saved to: a file, a descriptor, a
+/// memory buffer or any Rust type implementing `Write`.
+pub struct VipsTarget {
+    pub ptr: *mut vips_sys::VipsTarget
+}
+
+// === Traits ==================================================================
+
+impl Clone for VipsTarget {
+    fn clone(&self) -> Self {
+        unsafe { s::g_object_ref(self.ptr as *mut c_void) };
+
+        VipsTarget{ ptr: self.ptr }
+    }
+}
+
+impl Drop for VipsTarget {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { s::g_object_unref(self.ptr as *mut c_void) };
+        }
+    }
+}
+
+// === Custom behaviour ========================================================
+
+impl VipsTarget {
+    /// Wrap a `VipsTarget` around a pointer to a `vips_sys::VipsTarget`. The
+    /// same ownership rules as for `VipsImage::from_c_ptr` apply.
+    pub fn from_c_ptr(p: *mut vips_sys::VipsTarget) -> Result<VipsTarget, VipsError> {
+        if p.is_null() {
+            return Err(VipsError::new("Cannot wrap nullptr in VipsTarget"));
+        }
+        Ok(VipsTarget{ptr: p})
+    }
+
+    /// Create a target writing to the file descriptor `descriptor`.
+    ///
+    /// The descriptor is duplicated, so the caller remains responsible for
+    /// closing it.
+    pub fn new_to_descriptor(descriptor: i32) -> Result<VipsTarget, VipsError> {
+        let target = unsafe { s::vips_target_new_to_descriptor(descriptor) };
+        if target.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsTarget::from_c_ptr(target)
+    }
+
+    pub fn new_to_file(file: PathBuf) -> Result<VipsTarget, VipsError> {
+        let path_str = match file.to_str() {
+            Some(pstr) => pstr,
+            None => return Err(VipsError::new("Could not convert path to string"))
+        };
+        let path_c_str = CString::new(path_str)?;
+
+        let target = unsafe { s::vips_target_new_to_file(path_c_str.as_ptr()) };
+        if target.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsTarget::from_c_ptr(target)
+    }
+
+    /// Create a target collecting everything written to it in memory. Once an
+    /// image has been written, the data can be retrieved with `bytes()`.
+    pub fn new_to_memory() -> Result<VipsTarget, VipsError> {
+        let target = unsafe { s::vips_target_new_to_memory() };
+        if target.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsTarget::from_c_ptr(target)
+    }
+
+    /// Create a target writing to `writer`, e.g. a socket or an HTTP response
+    /// body. `writer` is flushed when Vips finishes writing an image.
+    pub fn new_to_writer<W: Write + Send + 'static>(writer: W) -> Result<VipsTarget, VipsError> {
+        unsafe {
+            let custom = s::vips_target_custom_new();
+            if custom.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+            // wrap it right away so it is unreffed if connecting fails
+            let target = VipsTarget::from_c_ptr(custom as *mut s::VipsTarget)?;
+
+            let writer: Box<dyn Write + Send> = Box::new(writer);
+            // the double box gives us a thin pointer to pass through glib
+            let data = Box::into_raw(Box::new(writer)) as *mut c_void;
+
+            if let Err(e) = signal_connect(
+                custom as *mut c_void,
+                "write",
+                std::mem::transmute::<
+                    unsafe extern "C" fn(*mut s::VipsTargetCustom, *const c_void, s::gint64, *mut c_void) -> s::gint64,
+                    unsafe extern "C" fn()
+                >(target_write_cb),
+                data,
+                Some(target_writer_destroy_cb)
+            ) {
+                drop(Box::from_raw(data as *mut Box<dyn Write + Send>));
+                return Err(e);
+            }
+
+            // the "write" handler owns `data`, see `VipsSource::new_custom`
+            signal_connect(
+                custom as *mut c_void,
+                "end",
+                std::mem::transmute::<
+                    unsafe extern "C" fn(*mut s::VipsTargetCustom, *mut c_void) -> c_int,
+                    unsafe extern "C" fn()
+                >(target_end_cb),
+                data,
+                None
+            )?;
+
+            Ok(target)
+        }
+    }
+
+    /// Get a copy of the data written to a target created with
+    /// `new_to_memory()`.
+    pub fn bytes(&self) -> Result<Vec<u8>, VipsError> {
+        unsafe { get_property(self.ptr as *mut s::GObject, "blob") }
+    }
+}
+
+// --- Custom target callbacks ---
+
+unsafe extern "C" fn target_write_cb(
+    _target: *mut s::VipsTargetCustom,
+    data: *const c_void,
+    length: s::gint64,
+    user_data: *mut c_void
+) -> s::gint64 {
+    let writer = &mut *(user_data as *mut Box<dyn Write + Send>);
+    let buf = std::slice::from_raw_parts(data as *const u8, length as usize);
+
+    match writer.write_all(buf) {
+        Ok(()) => length,
+        Err(_) => -1
+    }
+}
+
+unsafe extern "C" fn target_end_cb(
+    _target: *mut s::VipsTargetCustom,
+    user_data: *mut c_void
+) -> c_int {
+    let writer = &mut *(user_data as *mut Box<dyn Write + Send>);
+
+    match writer.flush() {
+        Ok(()) => 0,
+        Err(_) => -1
+    }
+}
+
+unsafe extern "C" fn target_writer_destroy_cb(data: *mut c_void, _closure: *mut s::GClosure) {
+    drop(Box::from_raw(data as *mut Box<dyn Write + Send>));
+}
+
+// =============================================================================
+// === VipsImage I/O ===========================================================
+// =============================================================================
+
+impl VipsImage {
+    /// Encode the image in the format given by `suffix` (e.g. `".tif"`) and
+    /// write it to `target`.
+    ///
+    /// `options` is passed on to the saver, e.g. `"compression=lzw"` for TIFF
+    /// files. Use `""` for no options.
+    pub fn write_to_target(&self, target: &VipsTarget, suffix: &str, options: &str) -> Result<(), VipsError> {
+        let suffix_c_str = if options.is_empty() {
+            CString::new(suffix)?
+        } else {
+            CString::new(format!("{suffix}[{options}]"))?
+        };
+
+        unsafe {
+            if s::vips_image_write_to_target(
+                self.ptr,
+                suffix_c_str.as_ptr(),
+                target.ptr,
+                std::ptr::null::<c_char>()
+            ) != 0 {
+                return Err(VipsError::new_from_vips_state());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn source_from_file() {
+        ensure_vips_init_or_exit();
+
+        let source = VipsSource::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Could not create source from file");
+        let img = VipsImage::new_from_source(&source, &LoadOptions::default())
+            .expect("Image could not be created from source");
+        assert_eq!(img.nbands(), 3);
+    }
+
+    #[test]
+    fn source_from_memory() {
+        ensure_vips_init_or_exit();
+
+        let data = std::fs::read("./data/test.jpg")
+            .expect("Could not read test file");
+        let source = VipsSource::new_from_memory(&data)
+            .expect("Could not create source from memory");
+        drop(data);
+
+        let img = VipsImage::new_from_source(&source, &LoadOptions::new().option_string("access=sequential"))
+            .expect("Image could not be created from source");
+        img.write_to_file(PathBuf::from("./data/test_from_source.jpg"))
+            .expect("Could not save image to file");
+    }
+
+    #[test]
+    fn source_from_reader() {
+        ensure_vips_init_or_exit();
+
+        let file = std::fs::File::open("./data/test.jpg")
+            .expect("Could not open test file");
+        let source = VipsSource::new_from_seekable(file)
+            .expect("Could not create source from seekable reader");
+        let img = VipsImage::new_from_source(&source, &LoadOptions::default())
+            .expect("Image could not be created from seekable reader");
+        assert_eq!(img.nbands(), 3);
+
+        let file = std::fs::File::open("./data/test.jpg")
+            .expect("Could not open test file");
+        let source = VipsSource::new_from_reader(file)
+            .expect("Could not create source from reader");
+        let img = VipsImage::new_from_source(&source, &LoadOptions::default())
+            .expect("Image could not be created from reader");
+        assert_eq!(img.nbands(), 3);
+    }
+
+    #[test]
+    fn target_to_memory() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let target = VipsTarget::new_to_memory()
+            .expect("Could not create memory target");
+        img.write_to_target(&target, ".png", "")
+            .expect("Could not write image to target");
+
+        let png = target.bytes().expect("Could not get data from target");
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+
+    #[test]
+    fn target_to_writer() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let file = std::fs::File::create("./data/test_to_writer.tif")
+            .expect("Could not create output file");
+        let target = VipsTarget::new_to_writer(file)
+            .expect("Could not create target from writer");
+        img.write_to_target(&target, ".tif", "compression=lzw")
+            .expect("Could not write image to target");
+        drop(target);
+
+        let reloaded = VipsImage::new_from_file(PathBuf::from("./data/test_to_writer.tif"))
+            .expect("Could not reload written image");
+        assert_eq!(reloaded.nbands(), img.nbands());
+    }
+}
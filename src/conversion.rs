@@ -0,0 +1,153 @@
+//! Joining, stacking and compositing images
+
+use crate::*;
+
+define_operator!(join, "in1", struct Args<'a> {
+    /// The image to place after `self`
+    pub in2: &'a VipsImage,
+    pub direction: Direction,
+    /// Make the output as large as the larger image instead of the smaller
+    pub expand: Option<bool>,
+    /// Space between the images
+    pub shim: Option<i32>,
+    /// Colour for the new pixels
+    pub background: Option<&'a [f64]>,
+    pub align: Option<Align>
+});
+
+define_array_operator!(arrayjoin, struct Args<'a> {
+    /// Number of images per row, by default all images go in one row
+    pub across: Option<i32>,
+    /// Space between the images
+    pub shim: Option<i32>,
+    /// Colour for the new pixels
+    pub background: Option<&'a [f64]>,
+    pub halign: Option<Align>,
+    pub valign: Option<Align>,
+    /// Horizontal distance between image origins, by default the widest width
+    pub hspacing: Option<i32>,
+    /// Vertical distance between image origins, by default the highest height
+    pub vspacing: Option<i32>
+});
+
+define_array_operator!(bandjoin);
+
+define_array_operator!(bandrank, struct Args {
+    /// Which of the sorted values to pick for each band, by default the median
+    pub index: Option<i32>
+});
+
+define_array_operator!(composite, struct Args<'a> {
+    /// How to blend each image onto the ones below, one fewer than there are
+    /// images
+    pub mode: &'a [BlendMode],
+    /// Horizontal position of each image after the first
+    pub x: Option<&'a [i32]>,
+    /// Vertical position of each image after the first
+    pub y: Option<&'a [i32]>,
+    /// The colour space to blend in
+    pub compositing_space: Option<Interpretation>,
+    /// The images already have premultiplied alpha
+    pub premultiplied: Option<bool>
+});
+
+define_array_operator!(switch, "tests");
+
+define_multi_output_operator!(smartcrop, "input", struct Args {
+    pub width: i32,
+    pub height: i32,
+    pub interesting: Option<Interesting>,
+    pub premultiplied: Option<bool>
+} => struct Output {
+    pub out: VipsImage,
+    /// Horizontal position of the most interesting point of the image
+    pub attention_x: i32,
+    /// Vertical position of the most interesting point of the image
+    pub attention_y: i32
+});
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant(value: f64) -> VipsImage {
+        VipsImage::new_matrix_from_array(2, 2, &[value; 4])
+            .expect("Could not create matrix")
+    }
+
+    #[test]
+    fn join_images() {
+        ensure_vips_init_or_exit();
+
+        let (a, b, c) = (constant(1.), constant(2.), constant(3.));
+
+        let joined = a.join(join::OpArgs{
+            in2: &b,
+            direction: Direction::Horizontal,
+            expand: None, shim: Some(1), background: None, align: None
+        }).expect("Could not join images");
+        assert_eq!((joined.width(), joined.height()), (5, 2));
+
+        let grid = VipsImage::arrayjoin(&[&a, &b, &c, &a], arrayjoin::OpArgs{
+            across: Some(2),
+            shim: None, background: None, halign: None, valign: None,
+            hspacing: None, vspacing: None
+        }).expect("Could not join array of images");
+        assert_eq!((grid.width(), grid.height()), (4, 4));
+
+        let stacked = VipsImage::bandjoin(&[&a, &b, &c]).expect("Could not join bands");
+        assert_eq!(stacked.nbands(), 3);
+    }
+
+    #[test]
+    fn combine_images() {
+        ensure_vips_init_or_exit();
+
+        let (a, b, c) = (constant(1.), constant(2.), constant(3.));
+
+        let sum = VipsImage::sum(&[&a, &b, &c]).expect("Could not sum images");
+        assert_eq!(sum.avg().expect("Could not get average"), 6.);
+
+        let median = VipsImage::bandrank(&[&c, &a, &b], bandrank::OpArgs{index: None})
+            .expect("Could not rank images");
+        assert_eq!(median.avg().expect("Could not get average"), 2.);
+
+        // the first test that is true picks the index, none true picks the
+        // number of tests
+        let tests = [&a.more_const1(5.).expect("Could not compare image")];
+        let switched = VipsImage::switch(&tests).expect("Could not switch images");
+        assert_eq!(switched.avg().expect("Could not get average"), 1.);
+
+        let img = VipsImage::new_from_file(std::path::PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        let composited = VipsImage::composite(&[&img, &img], composite::OpArgs{
+            mode: &[BlendMode::Over],
+            x: Some(&[10]), y: Some(&[10]),
+            compositing_space: None, premultiplied: None
+        }).expect("Could not composite images");
+        assert_eq!(composited.width(), img.width());
+    }
+
+    #[test]
+    fn smartcrop() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(std::path::PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let cropped = img.smartcrop(smartcrop::OpArgs{
+            width: 64,
+            height: 32,
+            interesting: Some(Interesting::Attention),
+            premultiplied: None
+        }).expect("Error while applying operation `smartcrop`");
+
+        assert_eq!((cropped.out.width(), cropped.out.height()), (64, 32));
+        assert!(cropped.attention_x >= 0 && (cropped.attention_x as usize) < img.width());
+        assert!(cropped.attention_y >= 0 && (cropped.attention_y as usize) < img.height());
+    }
+}
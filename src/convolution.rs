@@ -0,0 +1,49 @@
+//! Convolving images with a mask
+
+use crate::*;
+
+define_operator!(conv, struct Args<'a> {
+    pub mask: &'a VipsImage,
+    pub precision: Option<Precision>,
+    pub layers: Option<u32>,
+    pub cluster: Option<u32>
+});
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn convolve() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        assert_ne!(img.ptr, std::ptr::null_mut());
+
+        let kernel_size = 64;
+        let kernel = VipsImage::new_matrix(kernel_size, kernel_size)
+            .expect("Could not create kernel");
+
+        let kernel_n_px: f64 = (kernel_size * kernel_size).try_into().unwrap();
+        let kernel_v = 16f64 / kernel_n_px; // TODO: figure out why the kernel needs sum ~16
+        unsafe {
+            vips_sys::vips_draw_rect1(kernel.ptr,
+                kernel_v,
+                0, 0, kernel_size, kernel_size, 0);
+        }
+        let convolved = img.conv(conv::OpArgs{
+            mask: &kernel,
+            precision: None, layers: None, cluster: None
+        })
+            .expect("Error while applying operation `conv`");
+
+        convolved.write_to_file(PathBuf::from("./data/test_convolved.jpg"))
+            .expect("Could not save image to file");
+    }
+}
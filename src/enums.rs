@@ -0,0 +1,833 @@
+//! Rust counterparts of the enums used by Vips
+
+use std::fmt;
+use std::str::FromStr;
+
+use vips_sys as s;
+
+use crate::VipsError;
+use crate::operator::{FromGValue, GValue, ToGValue};
+
+/// Define a Rust enum mirroring a Vips enum, like:
+///
+/// ```ignore
+/// vips_enum!(
+///     /// The coding of an image
+///     Coding: s::VipsCoding {
+///         /// Pixels are not coded
+///         None = VipsCoding_VIPS_CODING_NONE => "none"
+///     }
+/// );
+/// ```
+///
+/// Each variant is mapped to the `vips_sys` constant after the `=` and to the
+/// nickname Vips uses for it in option strings after the `=>`. The enum
+/// converts to and from the raw type given after the `:` and to and from the
+/// nicknames with `Display` and `FromStr`.
+macro_rules! vips_enum {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $raw:ty {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $constant:ident => $nick:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant
+            ),*
+        }
+
+        impl $name {
+            /// Convert the raw value used by Vips. Returns `None` for values
+            /// that are not a member of the enum.
+            pub fn from_raw(raw: $raw) -> Option<$name> {
+                $(
+                    if raw == s::$constant {
+                        return Some($name::$variant);
+                    }
+                )*
+                None
+            }
+
+            /// The raw value used by Vips
+            pub fn to_raw(self) -> $raw {
+                match self {
+                    $( $name::$variant => s::$constant ),*
+                }
+            }
+
+            /// The nickname Vips uses for the value, e.g. in option strings
+            pub fn nick(self) -> &'static str {
+                match self {
+                    $( $name::$variant => $nick ),*
+                }
+            }
+
+            /// Read the value held by an enum or integer `GValue`
+            ///
+            /// # Safety
+            ///
+            /// `value` must be an initialised `GValue`.
+            pub unsafe fn from_gvalue(value: &s::GValue) -> Option<$name> {
+                let raw = if s::g_type_fundamental(value.g_type) == s::define_G_TYPE_ENUM {
+                    s::g_value_get_enum(value) as $raw
+                } else if value.g_type == s::define_G_TYPE_INT {
+                    s::g_value_get_int(value) as $raw
+                } else if value.g_type == s::define_G_TYPE_UINT {
+                    s::g_value_get_uint(value) as $raw
+                } else {
+                    return None;
+                };
+
+                $name::from_raw(raw)
+            }
+        }
+
+        impl From<$name> for $raw {
+            fn from(value: $name) -> $raw {
+                value.to_raw()
+            }
+        }
+
+        impl ToGValue for $name {
+            fn to_gvalue(&self) -> Option<GValue> {
+                self.to_raw().to_gvalue()
+            }
+        }
+
+        impl ToGValue for &[$name] {
+            fn to_gvalue(&self) -> Option<GValue> {
+                let raw: Vec<i32> = self.iter().map(|v| v.to_raw() as i32).collect();
+                raw.as_slice().to_gvalue()
+            }
+        }
+
+        impl FromGValue for $name {
+            unsafe fn from_gvalue(value: &s::GValue) -> Result<$name, VipsError> {
+                match $name::from_gvalue(value) {
+                    Some(v) => Ok(v),
+                    None => Err(VipsError::new(format!(
+                        "Value is not a {}", stringify!($name)
+                    )))
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.nick())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = VipsError;
+
+            fn from_str(nick: &str) -> Result<$name, VipsError> {
+                match nick {
+                    $( $nick => Ok($name::$variant), )*
+                    _ => Err(VipsError::new(format!(
+                        "Unknown {} `{nick}`", stringify!($name)
+                    )))
+                }
+            }
+        }
+    };
+}
+
+// for the enums in `generated`
+#[allow(unused_imports)]
+pub(crate) use vips_enum;
+
+/// Define a Rust type mirroring a Vips flags type, like:
+///
+/// ```ignore
+/// vips_flags!(
+///     /// Properties of a loaded image
+///     ForeignFlags: s::VipsForeignFlags {
+///         /// The image can be read in sections
+///         PARTIAL = VipsForeignFlags_VIPS_FOREIGN_PARTIAL
+///     }
+/// );
+/// ```
+///
+/// Each flag becomes an associated constant mapped to the `vips_sys` constant
+/// after the `=`. Flags are combined with `|` and tested with `contains`.
+macro_rules! vips_flags {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $raw:ty {
+            $(
+                $(#[$flag_meta:meta])*
+                $flag:ident = $constant:ident
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        pub struct $name($raw);
+
+        impl $name {
+            $(
+                $(#[$flag_meta])*
+                pub const $flag: $name = $name(s::$constant);
+            )*
+
+            /// No flags set
+            pub fn empty() -> $name {
+                $name(0)
+            }
+
+            /// Wrap the raw value used by Vips. Unknown bits are kept.
+            pub fn from_raw(raw: $raw) -> $name {
+                $name(raw)
+            }
+
+            /// The raw value used by Vips
+            pub fn to_raw(self) -> $raw {
+                self.0
+            }
+
+            /// Whether all flags set in `other` are also set in `self`
+            pub fn contains(self, other: $name) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl std::ops::BitOr for $name {
+            type Output = $name;
+
+            fn bitor(self, other: $name) -> $name {
+                $name(self.0 | other.0)
+            }
+        }
+
+        impl std::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, other: $name) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl From<$name> for $raw {
+            fn from(value: $name) -> $raw {
+                value.to_raw()
+            }
+        }
+
+        impl ToGValue for $name {
+            fn to_gvalue(&self) -> Option<GValue> {
+                self.to_raw().to_gvalue()
+            }
+        }
+
+        impl FromGValue for $name {
+            unsafe fn from_gvalue(value: &s::GValue) -> Result<$name, VipsError> {
+                Ok($name(<$raw as FromGValue>::from_gvalue(value)?))
+            }
+        }
+    };
+}
+
+// =============================================================================
+// === Image header ============================================================
+// =============================================================================
+
+vips_enum!(
+    /// The format of a single band value of a pixel
+    BandFormat: s::VipsBandFormat {
+        /// Invalid or unset format
+        NotSet = VipsBandFormat_VIPS_FORMAT_NOTSET => "notset",
+        /// Unsigned 8-bit integer
+        UChar = VipsBandFormat_VIPS_FORMAT_UCHAR => "uchar",
+        /// Signed 8-bit integer
+        Char = VipsBandFormat_VIPS_FORMAT_CHAR => "char",
+        /// Unsigned 16-bit integer
+        UShort = VipsBandFormat_VIPS_FORMAT_USHORT => "ushort",
+        /// Signed 16-bit integer
+        Short = VipsBandFormat_VIPS_FORMAT_SHORT => "short",
+        /// Unsigned 32-bit integer
+        UInt = VipsBandFormat_VIPS_FORMAT_UINT => "uint",
+        /// Signed 32-bit integer
+        Int = VipsBandFormat_VIPS_FORMAT_INT => "int",
+        /// 32-bit float
+        Float = VipsBandFormat_VIPS_FORMAT_FLOAT => "float",
+        /// Complex number made of two 32-bit floats
+        Complex = VipsBandFormat_VIPS_FORMAT_COMPLEX => "complex",
+        /// 64-bit float
+        Double = VipsBandFormat_VIPS_FORMAT_DOUBLE => "double",
+        /// Complex number made of two 64-bit floats
+        DpComplex = VipsBandFormat_VIPS_FORMAT_DPCOMPLEX => "dpcomplex"
+    }
+);
+
+vips_enum!(
+    /// How the pixels of an image are coded
+    Coding: s::VipsCoding {
+        /// Invalid coding
+        Error = VipsCoding_VIPS_CODING_ERROR => "error",
+        /// Pixels are not coded
+        None = VipsCoding_VIPS_CODING_NONE => "none",
+        /// Lab packed into 32 bits per pixel
+        LabQ = VipsCoding_VIPS_CODING_LABQ => "labq",
+        /// Radiance RGBE coding
+        Rad = VipsCoding_VIPS_CODING_RAD => "rad"
+    }
+);
+
+vips_enum!(
+    /// How the bands of an image should be interpreted
+    Interpretation: s::VipsInterpretation {
+        /// Invalid interpretation
+        Error = VipsInterpretation_VIPS_INTERPRETATION_ERROR => "error",
+        /// Any number of bands without a particular meaning
+        Multiband = VipsInterpretation_VIPS_INTERPRETATION_MULTIBAND => "multiband",
+        /// Greyscale, optionally with alpha
+        BW = VipsInterpretation_VIPS_INTERPRETATION_B_W => "b-w",
+        /// A 1xN or Nx1 histogram
+        Histogram = VipsInterpretation_VIPS_INTERPRETATION_HISTOGRAM => "histogram",
+        /// CIE XYZ
+        Xyz = VipsInterpretation_VIPS_INTERPRETATION_XYZ => "xyz",
+        /// CIE Lab
+        Lab = VipsInterpretation_VIPS_INTERPRETATION_LAB => "lab",
+        /// CMYK, optionally with alpha
+        Cmyk = VipsInterpretation_VIPS_INTERPRETATION_CMYK => "cmyk",
+        /// Lab packed into 32 bits, see `Coding::LabQ`
+        LabQ = VipsInterpretation_VIPS_INTERPRETATION_LABQ => "labq",
+        /// Generic RGB
+        Rgb = VipsInterpretation_VIPS_INTERPRETATION_RGB => "rgb",
+        /// CMC(l:c)
+        Cmc = VipsInterpretation_VIPS_INTERPRETATION_CMC => "cmc",
+        /// CIE LCh
+        Lch = VipsInterpretation_VIPS_INTERPRETATION_LCH => "lch",
+        /// CIE Lab stored as signed shorts
+        LabS = VipsInterpretation_VIPS_INTERPRETATION_LABS => "labs",
+        /// sRGB
+        Srgb = VipsInterpretation_VIPS_INTERPRETATION_sRGB => "srgb",
+        /// CIE Yxy
+        Yxy = VipsInterpretation_VIPS_INTERPRETATION_YXY => "yxy",
+        /// A Fourier transform
+        Fourier = VipsInterpretation_VIPS_INTERPRETATION_FOURIER => "fourier",
+        /// 16-bit RGB
+        Rgb16 = VipsInterpretation_VIPS_INTERPRETATION_RGB16 => "rgb16",
+        /// 16-bit greyscale
+        Grey16 = VipsInterpretation_VIPS_INTERPRETATION_GREY16 => "grey16",
+        /// A matrix, e.g. a convolution mask
+        Matrix = VipsInterpretation_VIPS_INTERPRETATION_MATRIX => "matrix",
+        /// Linear light sRGB
+        ScRgb = VipsInterpretation_VIPS_INTERPRETATION_scRGB => "scrgb",
+        /// HSV
+        Hsv = VipsInterpretation_VIPS_INTERPRETATION_HSV => "hsv"
+    }
+);
+
+// =============================================================================
+// === Colour ==================================================================
+// =============================================================================
+
+vips_enum!(
+    /// Rendering intent for ICC transforms
+    Intent: s::VipsIntent {
+        /// Keep the relationship between colours, compressing the gamut
+        Perceptual = VipsIntent_VIPS_INTENT_PERCEPTUAL => "perceptual",
+        /// Keep in-gamut colours exact and clip the rest, relative to the
+        /// white point
+        Relative = VipsIntent_VIPS_INTENT_RELATIVE => "relative",
+        /// Keep saturation at the expense of hue and lightness
+        Saturation = VipsIntent_VIPS_INTENT_SATURATION => "saturation",
+        /// Keep in-gamut colours exact, including the white point
+        Absolute = VipsIntent_VIPS_INTENT_ABSOLUTE => "absolute"
+    }
+);
+
+vips_enum!(
+    /// Profile connection space for ICC transforms
+    Pcs: s::VipsPCS {
+        /// CIE Lab
+        Lab = VipsPCS_VIPS_PCS_LAB => "lab",
+        /// CIE XYZ
+        Xyz = VipsPCS_VIPS_PCS_XYZ => "xyz"
+    }
+);
+
+// =============================================================================
+// === Geometry ================================================================
+// =============================================================================
+
+vips_enum!(
+    /// A clockwise rotation by a multiple of 90 degrees
+    Angle: s::VipsAngle {
+        /// No rotation
+        D0 = VipsAngle_VIPS_ANGLE_D0 => "d0",
+        /// 90 degrees clockwise
+        D90 = VipsAngle_VIPS_ANGLE_D90 => "d90",
+        /// 180 degrees
+        D180 = VipsAngle_VIPS_ANGLE_D180 => "d180",
+        /// 90 degrees anticlockwise
+        D270 = VipsAngle_VIPS_ANGLE_D270 => "d270"
+    }
+);
+
+vips_enum!(
+    /// Direction along an axis
+    Direction: s::VipsDirection {
+        /// Left to right
+        Horizontal = VipsDirection_VIPS_DIRECTION_HORIZONTAL => "horizontal",
+        /// Top to bottom
+        Vertical = VipsDirection_VIPS_DIRECTION_VERTICAL => "vertical"
+    }
+);
+
+vips_enum!(
+    /// How to generate pixels outside the image, e.g. when embedding it in a
+    /// larger image
+    Extend: s::VipsExtend {
+        /// Fill with zeros
+        Black = VipsExtend_VIPS_EXTEND_BLACK => "black",
+        /// Repeat the nearest edge pixel
+        Copy = VipsExtend_VIPS_EXTEND_COPY => "copy",
+        /// Tile the image
+        Repeat = VipsExtend_VIPS_EXTEND_REPEAT => "repeat",
+        /// Tile the image, mirroring every other tile
+        Mirror = VipsExtend_VIPS_EXTEND_MIRROR => "mirror",
+        /// Fill with the maximum value of the band format
+        White = VipsExtend_VIPS_EXTEND_WHITE => "white",
+        /// Fill with the background colour
+        Background = VipsExtend_VIPS_EXTEND_BACKGROUND => "background"
+    }
+);
+
+vips_enum!(
+    /// How to align images along an axis, e.g. when joining them
+    Align: s::VipsAlign {
+        /// Align the low coordinate edges, e.g. left or top
+        Low = VipsAlign_VIPS_ALIGN_LOW => "low",
+        /// Align the centres
+        Centre = VipsAlign_VIPS_ALIGN_CENTRE => "centre",
+        /// Align the high coordinate edges, e.g. right or bottom
+        High = VipsAlign_VIPS_ALIGN_HIGH => "high"
+    }
+);
+
+vips_enum!(
+    /// Resampling kernel for resizing
+    Kernel: s::VipsKernel {
+        /// Nearest neighbour
+        Nearest = VipsKernel_VIPS_KERNEL_NEAREST => "nearest",
+        /// Linear interpolation
+        Linear = VipsKernel_VIPS_KERNEL_LINEAR => "linear",
+        /// Catmull-Rom cubic
+        Cubic = VipsKernel_VIPS_KERNEL_CUBIC => "cubic",
+        /// Mitchell-Netravali cubic
+        Mitchell = VipsKernel_VIPS_KERNEL_MITCHELL => "mitchell",
+        /// Two-lobe Lanczos
+        Lanczos2 = VipsKernel_VIPS_KERNEL_LANCZOS2 => "lanczos2",
+        /// Three-lobe Lanczos
+        Lanczos3 = VipsKernel_VIPS_KERNEL_LANCZOS3 => "lanczos3"
+    }
+);
+
+vips_enum!(
+    /// How to pick the interesting part of an image when cropping
+    Interesting: s::VipsInteresting {
+        /// Keep the top left corner
+        None = VipsInteresting_VIPS_INTERESTING_NONE => "none",
+        /// Keep the centre
+        Centre = VipsInteresting_VIPS_INTERESTING_CENTRE => "centre",
+        /// Keep the region with the highest entropy
+        Entropy = VipsInteresting_VIPS_INTERESTING_ENTROPY => "entropy",
+        /// Keep the region most likely to draw attention
+        Attention = VipsInteresting_VIPS_INTERESTING_ATTENTION => "attention",
+        /// Keep the low coordinate end
+        Low = VipsInteresting_VIPS_INTERESTING_LOW => "low",
+        /// Keep the high coordinate end
+        High = VipsInteresting_VIPS_INTERESTING_HIGH => "high",
+        /// Keep everything
+        All = VipsInteresting_VIPS_INTERESTING_ALL => "all"
+    }
+);
+
+// =============================================================================
+// === Operations ==============================================================
+// =============================================================================
+
+vips_enum!(
+    /// Arithmetic precision of an operation
+    Precision: s::VipsPrecision {
+        /// Integer arithmetic
+        Integer = VipsPrecision_VIPS_PRECISION_INTEGER => "integer",
+        /// Floating point arithmetic
+        Float = VipsPrecision_VIPS_PRECISION_FLOAT => "float",
+        /// Fast approximation
+        Approximate = VipsPrecision_VIPS_PRECISION_APPROXIMATE => "approximate"
+    }
+);
+
+vips_enum!(
+    /// How to blend images when compositing, see the PDF blend modes
+    BlendMode: s::VipsBlendMode {
+        /// Clear the destination
+        Clear = VipsBlendMode_VIPS_BLEND_MODE_CLEAR => "clear",
+        /// Replace the destination
+        Source = VipsBlendMode_VIPS_BLEND_MODE_SOURCE => "source",
+        /// Draw the source over the destination
+        Over = VipsBlendMode_VIPS_BLEND_MODE_OVER => "over",
+        /// Draw the source where the destination is
+        In = VipsBlendMode_VIPS_BLEND_MODE_IN => "in",
+        /// Draw the source where the destination is not
+        Out = VipsBlendMode_VIPS_BLEND_MODE_OUT => "out",
+        /// Draw the source over the destination, only where the destination is
+        Atop = VipsBlendMode_VIPS_BLEND_MODE_ATOP => "atop",
+        /// Keep the destination
+        Dest = VipsBlendMode_VIPS_BLEND_MODE_DEST => "dest",
+        /// Draw the destination over the source
+        DestOver = VipsBlendMode_VIPS_BLEND_MODE_DEST_OVER => "dest-over",
+        /// Keep the destination where the source is
+        DestIn = VipsBlendMode_VIPS_BLEND_MODE_DEST_IN => "dest-in",
+        /// Keep the destination where the source is not
+        DestOut = VipsBlendMode_VIPS_BLEND_MODE_DEST_OUT => "dest-out",
+        /// Draw the destination over the source, only where the source is
+        DestAtop = VipsBlendMode_VIPS_BLEND_MODE_DEST_ATOP => "dest-atop",
+        /// Keep source and destination where they do not overlap
+        Xor = VipsBlendMode_VIPS_BLEND_MODE_XOR => "xor",
+        /// Add source and destination
+        Add = VipsBlendMode_VIPS_BLEND_MODE_ADD => "add",
+        /// Add the source as far as it fits
+        Saturate = VipsBlendMode_VIPS_BLEND_MODE_SATURATE => "saturate",
+        /// Multiply source and destination
+        Multiply = VipsBlendMode_VIPS_BLEND_MODE_MULTIPLY => "multiply",
+        /// Invert, multiply and invert again
+        Screen = VipsBlendMode_VIPS_BLEND_MODE_SCREEN => "screen",
+        /// Multiply or screen, depending on the destination
+        Overlay = VipsBlendMode_VIPS_BLEND_MODE_OVERLAY => "overlay",
+        /// Keep the darker of source and destination
+        Darken = VipsBlendMode_VIPS_BLEND_MODE_DARKEN => "darken",
+        /// Keep the lighter of source and destination
+        Lighten = VipsBlendMode_VIPS_BLEND_MODE_LIGHTEN => "lighten",
+        /// Brighten the destination to reflect the source
+        ColourDodge = VipsBlendMode_VIPS_BLEND_MODE_COLOUR_DODGE => "colour-dodge",
+        /// Darken the destination to reflect the source
+        ColourBurn = VipsBlendMode_VIPS_BLEND_MODE_COLOUR_BURN => "colour-burn",
+        /// Multiply or screen, depending on the source
+        HardLight = VipsBlendMode_VIPS_BLEND_MODE_HARD_LIGHT => "hard-light",
+        /// Darken or lighten, depending on the source
+        SoftLight = VipsBlendMode_VIPS_BLEND_MODE_SOFT_LIGHT => "soft-light",
+        /// Subtract the darker from the lighter
+        Difference = VipsBlendMode_VIPS_BLEND_MODE_DIFFERENCE => "difference",
+        /// Like difference, with lower contrast
+        Exclusion = VipsBlendMode_VIPS_BLEND_MODE_EXCLUSION => "exclusion"
+    }
+);
+
+vips_enum!(
+    /// Unary maths function for `math`
+    OperationMath: s::VipsOperationMath {
+        /// Sine, in degrees
+        Sin = VipsOperationMath_VIPS_OPERATION_MATH_SIN => "sin",
+        /// Cosine, in degrees
+        Cos = VipsOperationMath_VIPS_OPERATION_MATH_COS => "cos",
+        /// Tangent, in degrees
+        Tan = VipsOperationMath_VIPS_OPERATION_MATH_TAN => "tan",
+        /// Arc sine, in degrees
+        Asin = VipsOperationMath_VIPS_OPERATION_MATH_ASIN => "asin",
+        /// Arc cosine, in degrees
+        Acos = VipsOperationMath_VIPS_OPERATION_MATH_ACOS => "acos",
+        /// Arc tangent, in degrees
+        Atan = VipsOperationMath_VIPS_OPERATION_MATH_ATAN => "atan",
+        /// Natural logarithm
+        Log = VipsOperationMath_VIPS_OPERATION_MATH_LOG => "log",
+        /// Base 10 logarithm
+        Log10 = VipsOperationMath_VIPS_OPERATION_MATH_LOG10 => "log10",
+        /// e to the power of the pixel
+        Exp = VipsOperationMath_VIPS_OPERATION_MATH_EXP => "exp",
+        /// 10 to the power of the pixel
+        Exp10 = VipsOperationMath_VIPS_OPERATION_MATH_EXP10 => "exp10",
+        /// Hyperbolic sine
+        Sinh = VipsOperationMath_VIPS_OPERATION_MATH_SINH => "sinh",
+        /// Hyperbolic cosine
+        Cosh = VipsOperationMath_VIPS_OPERATION_MATH_COSH => "cosh",
+        /// Hyperbolic tangent
+        Tanh = VipsOperationMath_VIPS_OPERATION_MATH_TANH => "tanh",
+        /// Inverse hyperbolic sine
+        Asinh = VipsOperationMath_VIPS_OPERATION_MATH_ASINH => "asinh",
+        /// Inverse hyperbolic cosine
+        Acosh = VipsOperationMath_VIPS_OPERATION_MATH_ACOSH => "acosh",
+        /// Inverse hyperbolic tangent
+        Atanh = VipsOperationMath_VIPS_OPERATION_MATH_ATANH => "atanh"
+    }
+);
+
+vips_enum!(
+    /// Binary maths function for `math2`
+    OperationMath2: s::VipsOperationMath2 {
+        /// Left to the power of right
+        Pow = VipsOperationMath2_VIPS_OPERATION_MATH2_POW => "pow",
+        /// Right to the power of left
+        Wop = VipsOperationMath2_VIPS_OPERATION_MATH2_WOP => "wop",
+        /// Arc tangent of left / right, in degrees
+        Atan2 = VipsOperationMath2_VIPS_OPERATION_MATH2_ATAN2 => "atan2"
+    }
+);
+
+vips_enum!(
+    /// Rounding mode for `round`
+    OperationRound: s::VipsOperationRound {
+        /// Round to the nearest integer
+        Rint = VipsOperationRound_VIPS_OPERATION_ROUND_RINT => "rint",
+        /// Round up
+        Ceil = VipsOperationRound_VIPS_OPERATION_ROUND_CEIL => "ceil",
+        /// Round down
+        Floor = VipsOperationRound_VIPS_OPERATION_ROUND_FLOOR => "floor"
+    }
+);
+
+vips_enum!(
+    /// Comparison for `relational`
+    OperationRelational: s::VipsOperationRelational {
+        /// `==`
+        Equal = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_EQUAL => "equal",
+        /// `!=`
+        NotEq = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_NOTEQ => "noteq",
+        /// `<`
+        Less = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_LESS => "less",
+        /// `<=`
+        LessEq = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_LESSEQ => "lesseq",
+        /// `>`
+        More = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_MORE => "more",
+        /// `>=`
+        MoreEq = VipsOperationRelational_VIPS_OPERATION_RELATIONAL_MOREEQ => "moreeq"
+    }
+);
+
+vips_enum!(
+    /// Bitwise operation for `boolean`
+    OperationBoolean: s::VipsOperationBoolean {
+        /// `&`
+        And = VipsOperationBoolean_VIPS_OPERATION_BOOLEAN_AND => "and",
+        /// `|`
+        Or = VipsOperationBoolean_VIPS_OPERATION_BOOLEAN_OR => "or",
+        /// `^`
+        Eor = VipsOperationBoolean_VIPS_OPERATION_BOOLEAN_EOR => "eor",
+        /// `<<`
+        LShift = VipsOperationBoolean_VIPS_OPERATION_BOOLEAN_LSHIFT => "lshift",
+        /// `>>`
+        RShift = VipsOperationBoolean_VIPS_OPERATION_BOOLEAN_RSHIFT => "rshift"
+    }
+);
+
+vips_enum!(
+    /// Unary complex operation for `complex`
+    OperationComplex: s::VipsOperationComplex {
+        /// Convert to polar coordinates
+        Polar = VipsOperationComplex_VIPS_OPERATION_COMPLEX_POLAR => "polar",
+        /// Convert to rectangular coordinates
+        Rect = VipsOperationComplex_VIPS_OPERATION_COMPLEX_RECT => "rect",
+        /// Complex conjugate
+        Conj = VipsOperationComplex_VIPS_OPERATION_COMPLEX_CONJ => "conj"
+    }
+);
+
+vips_enum!(
+    /// Binary complex operation for `complex2`
+    OperationComplex2: s::VipsOperationComplex2 {
+        /// Cross phase
+        CrossPhase = VipsOperationComplex2_VIPS_OPERATION_COMPLEX2_CROSS_PHASE => "cross-phase"
+    }
+);
+
+vips_enum!(
+    /// Component to extract with `complexget`
+    OperationComplexget: s::VipsOperationComplexget {
+        /// Real part
+        Real = VipsOperationComplexget_VIPS_OPERATION_COMPLEXGET_REAL => "real",
+        /// Imaginary part
+        Imag = VipsOperationComplexget_VIPS_OPERATION_COMPLEXGET_IMAG => "imag"
+    }
+);
+
+// =============================================================================
+// === Loading and saving ======================================================
+// =============================================================================
+
+vips_enum!(
+    /// How a loader will read the pixels of an image
+    Access: s::VipsAccess {
+        /// Pixels can be read in any order
+        Random = VipsAccess_VIPS_ACCESS_RANDOM => "random",
+        /// Pixels are read top to bottom
+        Sequential = VipsAccess_VIPS_ACCESS_SEQUENTIAL => "sequential",
+        /// Pixels are read top to bottom without a line cache
+        SequentialUnbuffered = VipsAccess_VIPS_ACCESS_SEQUENTIAL_UNBUFFERED => "sequential-unbuffered"
+    }
+);
+
+vips_enum!(
+    /// The kind of problem with the input that makes a load fail
+    FailOn: s::VipsFailOn {
+        /// Never fail
+        None = VipsFailOn_VIPS_FAIL_ON_NONE => "none",
+        /// Fail on truncated images
+        Truncated = VipsFailOn_VIPS_FAIL_ON_TRUNCATED => "truncated",
+        /// Fail on errors
+        Error = VipsFailOn_VIPS_FAIL_ON_ERROR => "error",
+        /// Fail on anything, even warnings
+        Warning = VipsFailOn_VIPS_FAIL_ON_WARNING => "warning"
+    }
+);
+
+vips_flags!(
+    /// Properties a loader reports for its input
+    ForeignFlags: s::VipsForeignFlags {
+        /// The image can be read in sections without decoding all of it
+        PARTIAL = VipsForeignFlags_VIPS_FOREIGN_PARTIAL,
+        /// The pixel data is stored in big-endian byte order
+        BIGENDIAN = VipsForeignFlags_VIPS_FOREIGN_BIGENDIAN,
+        /// The image can be read efficiently top-to-bottom
+        SEQUENTIAL = VipsForeignFlags_VIPS_FOREIGN_SEQUENTIAL
+    }
+);
+
+vips_flags!(
+    /// Row filters the PNG saver may try
+    ForeignPngFilter: s::VipsForeignPngFilter {
+        /// No filtering
+        NONE = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_NONE,
+        /// Difference to the pixel on the left
+        SUB = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_SUB,
+        /// Difference to the pixel above
+        UP = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_UP,
+        /// Difference to the average of the pixels on the left and above
+        AVG = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_AVG,
+        /// Paeth predictor
+        PAETH = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_PAETH,
+        /// All of the filters
+        ALL = VipsForeignPngFilter_VIPS_FOREIGN_PNG_FILTER_ALL
+    }
+);
+
+vips_enum!(
+    /// Chroma subsampling mode for savers
+    ForeignSubsample: s::VipsForeignSubsample {
+        /// Subsample unless the quality is high
+        Auto = VipsForeignSubsample_VIPS_FOREIGN_SUBSAMPLE_AUTO => "auto",
+        /// Always subsample
+        On = VipsForeignSubsample_VIPS_FOREIGN_SUBSAMPLE_ON => "on",
+        /// Never subsample
+        Off = VipsForeignSubsample_VIPS_FOREIGN_SUBSAMPLE_OFF => "off"
+    }
+);
+
+vips_enum!(
+    /// Encoder preset for WebP
+    WebpPreset: s::VipsForeignWebpPreset {
+        /// Generic preset
+        Default = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_DEFAULT => "default",
+        /// Digital pictures, like portraits or indoor shots
+        Picture = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_PICTURE => "picture",
+        /// Outdoor photographs with natural lighting
+        Photo = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_PHOTO => "photo",
+        /// Hand or line drawings with high contrast
+        Drawing = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_DRAWING => "drawing",
+        /// Small colourful images
+        Icon = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_ICON => "icon",
+        /// Text-like images
+        Text = VipsForeignWebpPreset_VIPS_FOREIGN_WEBP_PRESET_TEXT => "text"
+    }
+);
+
+vips_enum!(
+    /// Compression scheme for TIFF
+    TiffCompression: s::VipsForeignTiffCompression {
+        /// No compression
+        None = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_NONE => "none",
+        /// JPEG compression
+        Jpeg = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_JPEG => "jpeg",
+        /// Deflate (zip) compression
+        Deflate = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_DEFLATE => "deflate",
+        /// PackBits compression
+        Packbits = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_PACKBITS => "packbits",
+        /// CCITT group 4 fax compression, for 1-bit images
+        Ccittfax4 = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_CCITTFAX4 => "ccittfax4",
+        /// LZW compression
+        Lzw = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_LZW => "lzw",
+        /// WebP compression
+        Webp = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_WEBP => "webp",
+        /// Zstandard compression
+        Zstd = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_ZSTD => "zstd",
+        /// JPEG 2000 compression
+        Jp2k = VipsForeignTiffCompression_VIPS_FOREIGN_TIFF_COMPRESSION_JP2K => "jp2k"
+    }
+);
+
+vips_enum!(
+    /// Predictor for TIFF compression
+    TiffPredictor: s::VipsForeignTiffPredictor {
+        /// No prediction
+        None = VipsForeignTiffPredictor_VIPS_FOREIGN_TIFF_PREDICTOR_NONE => "none",
+        /// Horizontal differencing
+        Horizontal = VipsForeignTiffPredictor_VIPS_FOREIGN_TIFF_PREDICTOR_HORIZONTAL => "horizontal",
+        /// Floating point prediction
+        Float = VipsForeignTiffPredictor_VIPS_FOREIGN_TIFF_PREDICTOR_FLOAT => "float"
+    }
+);
+
+vips_enum!(
+    /// Compression format for HEIF
+    HeifCompression: s::VipsForeignHeifCompression {
+        /// HEVC, i.e. HEIC files
+        Hevc = VipsForeignHeifCompression_VIPS_FOREIGN_HEIF_COMPRESSION_HEVC => "hevc",
+        /// AVC
+        Avc = VipsForeignHeifCompression_VIPS_FOREIGN_HEIF_COMPRESSION_AVC => "avc",
+        /// JPEG
+        Jpeg = VipsForeignHeifCompression_VIPS_FOREIGN_HEIF_COMPRESSION_JPEG => "jpeg",
+        /// AV1, i.e. AVIF files
+        Av1 = VipsForeignHeifCompression_VIPS_FOREIGN_HEIF_COMPRESSION_AV1 => "av1"
+    }
+);
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enum_raw_roundtrip() {
+        assert_eq!(BandFormat::from_raw(BandFormat::Float.to_raw()), Some(BandFormat::Float));
+        assert_eq!(s::VipsCoding::from(Coding::LabQ), s::VipsCoding_VIPS_CODING_LABQ);
+        assert_eq!(
+            Interpretation::from_raw(s::VipsInterpretation_VIPS_INTERPRETATION_sRGB),
+            Some(Interpretation::Srgb)
+        );
+        assert_eq!(Interpretation::from_raw(1000), None);
+    }
+
+    #[test]
+    fn enum_nicks() {
+        assert_eq!(Interpretation::Srgb.to_string(), "srgb");
+        assert_eq!("b-w".parse::<Interpretation>().expect("Unknown nick"), Interpretation::BW);
+        assert_eq!("dest-over".parse::<BlendMode>().expect("Unknown nick"), BlendMode::DestOver);
+        assert_eq!(Access::SequentialUnbuffered.nick(), "sequential-unbuffered");
+        assert!("not-a-format".parse::<BandFormat>().is_err());
+    }
+
+    #[test]
+    fn flags() {
+        let filter = ForeignPngFilter::SUB | ForeignPngFilter::UP;
+        assert!(filter.contains(ForeignPngFilter::UP));
+        assert!(!filter.contains(ForeignPngFilter::PAETH));
+        assert!(ForeignPngFilter::ALL.contains(filter));
+        assert_eq!(
+            ForeignFlags::from_raw(s::VipsForeignFlags_VIPS_FOREIGN_ALL),
+            ForeignFlags::PARTIAL | ForeignFlags::BIGENDIAN | ForeignFlags::SEQUENTIAL
+        );
+        assert!(ForeignFlags::empty().contains(ForeignFlags::empty()));
+    }
+}
@@ -1,28 +1,86 @@
 use std::ffi::CStr;
 
+// =============================================================================
+// === ErrorKind ===============================================================
+// =============================================================================
+
+/// The broad category of a `VipsError`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A loader could not read the image
+    LoadFailed,
+    /// No loader or saver supports the file format
+    UnsupportedFormat,
+    /// An argument was missing, unknown or out of range
+    InvalidArgument,
+    /// Memory could not be allocated
+    OutOfMemory,
+    /// The computation was cancelled
+    Cancelled,
+    /// A file, descriptor or stream could not be read or written
+    Io,
+    /// Any other error
+    Other
+}
+
 // =============================================================================
 // === VipsError ===============================================================
 // =============================================================================
 
+/// A single line from the Vips error buffer, like
+/// `"VipsForeignLoad: file "x.jpg" does not exist"`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VipsErrorLine {
+    /// The part of Vips that reported the error, e.g. `"VipsForeignLoad"`
+    pub domain: String,
+    pub message: String
+}
+
 #[derive(Debug)]
 pub struct VipsError {
-    description: String
+    kind: ErrorKind,
+    description: String,
+    operation: Option<String>,
+    argument: Option<String>,
+    lines: Vec<VipsErrorLine>,
+    source: Option<Box<dyn std::error::Error + Send + Sync>>
 }
 
 // === Traits ==================================================================
 
 impl std::fmt::Display for VipsError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.operation, &self.argument) {
+            (Some(operation), Some(argument)) =>
+                f.write_fmt(format_args!("{operation} (argument `{argument}`): ")),
+            (Some(operation), None) => f.write_fmt(format_args!("{operation}: ")),
+            (None, Some(argument)) => f.write_fmt(format_args!("argument `{argument}`: ")),
+            (None, None) => Ok(())
+        }?;
         f.write_fmt(format_args!("{}", self.description))
     }
 }
 
 impl std::error::Error for VipsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|source| source.as_ref() as &(dyn std::error::Error + 'static))
+    }
 }
 
 impl From<std::ffi::NulError> for VipsError {
     fn from(err: std::ffi::NulError) -> Self {
         VipsError::new(err.to_string())
+            .with_kind(ErrorKind::InvalidArgument)
+            .with_source(err)
+    }
+}
+
+impl From<std::io::Error> for VipsError {
+    fn from(err: std::io::Error) -> Self {
+        VipsError::new(err.to_string())
+            .with_kind(ErrorKind::Io)
+            .with_source(err)
     }
 }
 
@@ -31,17 +89,229 @@ impl From<std::ffi::NulError> for VipsError {
 impl VipsError {
     pub fn description(&self) -> &str { self.description.as_str() }
 
+    pub fn kind(&self) -> ErrorKind { self.kind }
+
+    /// Nickname of the operation that failed, e.g. `"jpegload"`
+    pub fn operation(&self) -> Option<&str> { self.operation.as_deref() }
+
+    /// Name of the operation argument that caused the error
+    pub fn argument(&self) -> Option<&str> { self.argument.as_deref() }
+
+    /// The lines Vips reported for the error. Empty for errors raised by
+    /// this crate.
+    pub fn lines(&self) -> &[VipsErrorLine] { self.lines.as_slice() }
+
     pub fn new<T: Into<String>>(description: T) -> VipsError {
-        VipsError { description: description.into() }
+        VipsError {
+            kind: ErrorKind::Other,
+            description: description.into(),
+            operation: None,
+            argument: None,
+            lines: Vec::new(),
+            source: None
+        }
     }
 
+    /// Take the contents of the Vips error buffer and clear it, so the error
+    /// does not show up again in later errors.
+    ///
+    /// The buffer is shared by all threads of the process. It is read and
+    /// cleared in one step, but Vips appends to it from every thread, so when
+    /// operations fail on several threads at once this error may hold lines
+    /// reported by another thread, or miss its own lines because another
+    /// thread took them first. The kind, which is guessed from the lines, is
+    /// affected the same way.
     pub fn new_from_vips_state() -> VipsError {
-        let err_descr = unsafe {
-            CStr::from_ptr(vips_sys::vips_error_buffer())
+        let buffer = unsafe {
+            let p = vips_sys::vips_error_buffer_copy();
+            if p.is_null() {
+                String::new()
+            } else {
+                let buffer = CStr::from_ptr(p).to_string_lossy().into_owned();
+                vips_sys::g_free(p as *mut std::ffi::c_void);
+                buffer
+            }
+        };
+
+        VipsError::from_error_buffer(&buffer)
+    }
+
+    /// Build an error from the text of the Vips error buffer
+    fn from_error_buffer(buffer: &str) -> VipsError {
+        let lines: Vec<VipsErrorLine> = buffer.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| match line.split_once(": ") {
+                Some((domain, message)) => VipsErrorLine {
+                    domain: domain.to_string(),
+                    message: message.to_string()
+                },
+                None => VipsErrorLine {
+                    domain: String::new(),
+                    message: line.to_string()
+                }
+            })
+            .collect();
+
+        let description = match buffer.trim_end() {
+            "" => "Unknown Vips error".to_string(),
+            description => description.to_string()
         };
-        match err_descr.to_str() {
-            Ok(err_str) => VipsError::new(err_str),
-            Err(e) => VipsError::new(format!("Error parsing Vips error buffer: `{e}`"))
+
+        let mut error = VipsError::new(description);
+        error.kind = classify(&lines);
+        error.lines = lines;
+        error
+    }
+
+    pub fn with_kind(mut self, kind: ErrorKind) -> VipsError {
+        self.kind = kind;
+        self
+    }
+
+    /// Set the kind only if the error could not be classified otherwise
+    pub(crate) fn with_default_kind(mut self, kind: ErrorKind) -> VipsError {
+        if self.kind == ErrorKind::Other {
+            self.kind = kind;
         }
+        self
+    }
+
+    pub fn with_operation<T: Into<String>>(mut self, operation: T) -> VipsError {
+        self.operation = Some(operation.into());
+        self
+    }
+
+    pub fn with_argument<T: Into<String>>(mut self, argument: T) -> VipsError {
+        self.argument = Some(argument.into());
+        self
+    }
+
+    pub fn with_source<E>(mut self, source: E) -> VipsError
+    where E: std::error::Error + Send + Sync + 'static {
+        self.source = Some(Box::new(source));
+        self
+    }
+}
+
+// === Helpers =================================================================
+
+/// Serialise the tests that make Vips report errors. Tests run in parallel and
+/// would otherwise read each other's lines from the shared error buffer, so
+/// the buffer is also cleared of anything left behind.
+#[cfg(test)]
+pub(crate) fn lock_error_buffer() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    unsafe { vips_sys::vips_error_clear() };
+    guard
+}
+
+/// Guess the kind of an error from the lines Vips reported. Vips has no error
+/// codes, so this has to go by the messages.
+fn classify(lines: &[VipsErrorLine]) -> ErrorKind {
+    let matches = |patterns: &[&str]| lines.iter().any(|line| {
+        let message = line.message.to_lowercase();
+        patterns.iter().any(|pattern| message.contains(pattern))
+    });
+
+    if matches(&["out of memory", "unable to allocate", "failed to allocate"]) {
+        ErrorKind::OutOfMemory
+    } else if matches(&["killed", "cancelled", "canceled"]) {
+        ErrorKind::Cancelled
+    } else if matches(&[
+        "not a known file format", "not in a known format", "not a known target format",
+        "no known saver"
+    ]) || lines.iter().any(|line| {
+        // operations also report unsupported band formats and the like, so
+        // only loaders and savers mean the file format
+        line.domain.starts_with("VipsForeign") && line.message.to_lowercase().contains("unsupported")
+    }) {
+        ErrorKind::UnsupportedFormat
+    } else if matches(&[
+        "does not exist", "unable to open", "unable to read", "unable to write",
+        "no such file", "read error", "write error"
+    ]) {
+        ErrorKind::Io
+    } else if matches(&[
+        "parameter", "no property named", "out of range", "bad argument",
+        "invalid argument", "not set"
+    ]) {
+        ErrorKind::InvalidArgument
+    } else if lines.iter().any(|line| line.domain.contains("load") || line.domain.contains("Load")) {
+        ErrorKind::LoadFailed
+    } else {
+        ErrorKind::Other
+    }
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn error_buffer_parsing() {
+        let error = VipsError::from_error_buffer(
+            "VipsForeignLoad: \"x.foo\" is not a known file format\n"
+        );
+        assert_eq!(error.kind(), ErrorKind::UnsupportedFormat);
+        assert_eq!(error.lines(), &[VipsErrorLine {
+            domain: "VipsForeignLoad".to_string(),
+            message: "\"x.foo\" is not a known file format".to_string()
+        }]);
+
+        let error = VipsError::from_error_buffer(
+            "jpegload: Premature end of JPEG file\njpegload: out of order read at line 12\n"
+        );
+        assert_eq!(error.kind(), ErrorKind::LoadFailed);
+        assert_eq!(error.lines().len(), 2);
+
+        let error = VipsError::from_error_buffer("VipsForeignSave: unsupported image format\n");
+        assert_eq!(error.kind(), ErrorKind::UnsupportedFormat);
+
+        let error = VipsError::from_error_buffer("linear: unsupported band format\n");
+        assert_eq!(error.kind(), ErrorKind::Other);
+
+        let error = VipsError::from_error_buffer("");
+        assert_eq!(error.kind(), ErrorKind::Other);
+        assert!(error.lines().is_empty());
+    }
+
+    #[test]
+    fn error_context() {
+        let error = VipsError::new("boom")
+            .with_operation("add")
+            .with_argument("right");
+        assert_eq!(error.to_string(), "add (argument `right`): boom");
+
+        let error = VipsError::from(std::io::Error::other("disk full"));
+        assert_eq!(error.kind(), ErrorKind::Io);
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn error_from_vips() {
+        ensure_vips_init_or_exit();
+        let _errors = lock_error_buffer();
+
+        let error = match VipsImage::new_from_file(PathBuf::from("./data/no-such-file.jpg")) {
+            Ok(_) => panic!("Loading a missing file should error"),
+            Err(e) => e
+        };
+        assert_eq!(error.kind(), ErrorKind::Io);
+        assert!(!error.lines().is_empty());
+
+        // the buffer was cleared, so the next error only holds its own lines
+        let error = match VipsImage::new_from_buffer(b"definitely not an image", &LoadOptions::default()) {
+            Ok(_) => panic!("Loading an unknown format should error"),
+            Err(e) => e
+        };
+        assert_eq!(error.kind(), ErrorKind::UnsupportedFormat);
+        assert!(!error.description().contains("no-such-file"));
     }
 }
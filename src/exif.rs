@@ -0,0 +1,384 @@
+//! Structured access to the EXIF metadata of images
+//!
+//! Vips parses the EXIF block of an image into one metadata field per tag,
+//! named like `"exif-ifd0-Make"`. Changes to these fields are written back to
+//! the EXIF block when the image is saved.
+
+use crate::*;
+use crate::operator::{call_operation, get_property};
+
+// =============================================================================
+// === Exif ====================================================================
+// =============================================================================
+
+/// A position from the EXIF GPS fields in decimal degrees. Southern latitudes
+/// and western longitudes are negative.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GpsPosition {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Altitude in metres, negative below sea level
+    pub altitude: Option<f64>
+}
+
+/// The commonly used EXIF fields of an image. Fields the image does not have
+/// are `None`.
+///
+/// Use `VipsImage::exif_field` for tags not covered here.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Exif {
+    /// Manufacturer of the camera
+    pub make: Option<String>,
+    /// Model of the camera
+    pub model: Option<String>,
+    /// Software that created the image
+    pub software: Option<String>,
+    /// When the file was last changed, as `"YYYY:MM:DD HH:MM:SS"`
+    pub date_time: Option<String>,
+    /// When the image was captured, as `"YYYY:MM:DD HH:MM:SS"`
+    pub date_time_original: Option<String>,
+    /// Orientation of the image, 1 - 8
+    pub orientation: Option<i32>,
+    /// Exposure time in seconds
+    pub exposure_time: Option<f64>,
+    /// F-number of the aperture
+    pub f_number: Option<f64>,
+    /// ISO speed
+    pub iso: Option<i32>,
+    /// Focal length of the lens in millimetres
+    pub focal_length: Option<f64>,
+    /// Where the image was captured
+    pub gps: Option<GpsPosition>
+}
+
+/// The result of `autorot`: how the image was transformed to make it upright
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Autorotation {
+    /// Clockwise rotation that was applied
+    pub angle: Angle,
+    /// Whether the image was flipped horizontally before rotating
+    pub flip: bool
+}
+
+// === Field names =============================================================
+
+const MAKE: &str = "exif-ifd0-Make";
+const MODEL: &str = "exif-ifd0-Model";
+const SOFTWARE: &str = "exif-ifd0-Software";
+const DATE_TIME: &str = "exif-ifd0-DateTime";
+const ORIENTATION: &str = "exif-ifd0-Orientation";
+const DATE_TIME_ORIGINAL: &str = "exif-ifd2-DateTimeOriginal";
+const EXPOSURE_TIME: &str = "exif-ifd2-ExposureTime";
+const F_NUMBER: &str = "exif-ifd2-FNumber";
+const ISO: &str = "exif-ifd2-ISOSpeedRatings";
+const FOCAL_LENGTH: &str = "exif-ifd2-FocalLength";
+const GPS_LATITUDE: &str = "exif-ifd3-GPSLatitude";
+const GPS_LATITUDE_REF: &str = "exif-ifd3-GPSLatitudeRef";
+const GPS_LONGITUDE: &str = "exif-ifd3-GPSLongitude";
+const GPS_LONGITUDE_REF: &str = "exif-ifd3-GPSLongitudeRef";
+const GPS_ALTITUDE: &str = "exif-ifd3-GPSAltitude";
+const GPS_ALTITUDE_REF: &str = "exif-ifd3-GPSAltitudeRef";
+
+// =============================================================================
+// === VipsImage ===============================================================
+// =============================================================================
+
+impl VipsImage {
+    /// The raw EXIF block of the image, if it has one
+    pub fn exif_data(&self) -> Option<Vec<u8>> {
+        if !self.has_field("exif-data") {
+            return None;
+        }
+
+        match self.get("exif-data") {
+            Ok(MetaValue::Blob(data)) => Some(data),
+            _ => None
+        }
+    }
+
+    /// The value of the EXIF field `name`, e.g. `"exif-ifd0-Make"`, without
+    /// the human-readable description Vips appends to values it parsed.
+    /// Rationals are returned as `"numerator/denominator"`.
+    pub fn exif_field(&self, name: &str) -> Option<String> {
+        if !self.has_field(name) {
+            return None;
+        }
+
+        match self.get(name) {
+            Ok(MetaValue::RefString(value)) | Ok(MetaValue::String(value)) =>
+                Some(raw_exif_value(&value).to_string()),
+            _ => None
+        }
+    }
+
+    /// Set the EXIF field `name` to `value`, in the format returned by
+    /// `exif_field`. The EXIF block is updated when the image is saved.
+    pub fn set_exif_field(&mut self, name: &str, value: &str) -> Result<(), VipsError> {
+        self.set(name, MetaValue::RefString(value.to_string()))
+    }
+
+    /// Parse the commonly used EXIF fields of the image
+    pub fn exif(&self) -> Exif {
+        let rational = |name| self.exif_field(name).and_then(|v| parse_rationals(&v)?.first().copied());
+
+        let gps = match (
+            self.exif_field(GPS_LATITUDE).and_then(|v| parse_degrees(&v)),
+            self.exif_field(GPS_LONGITUDE).and_then(|v| parse_degrees(&v))
+        ) {
+            (Some(latitude), Some(longitude)) => {
+                let latitude_ref = self.exif_field(GPS_LATITUDE_REF);
+                let longitude_ref = self.exif_field(GPS_LONGITUDE_REF);
+                let below_sea_level = self.exif_field(GPS_ALTITUDE_REF)
+                    .is_some_and(|v| v.trim() == "1");
+
+                Some(GpsPosition {
+                    latitude: if latitude_ref.as_deref() == Some("S") { -latitude } else { latitude },
+                    longitude: if longitude_ref.as_deref() == Some("W") { -longitude } else { longitude },
+                    altitude: rational(GPS_ALTITUDE)
+                        .map(|altitude| if below_sea_level { -altitude } else { altitude })
+                })
+            },
+            _ => None
+        };
+
+        Exif {
+            make: self.exif_field(MAKE),
+            model: self.exif_field(MODEL),
+            software: self.exif_field(SOFTWARE),
+            date_time: self.exif_field(DATE_TIME),
+            date_time_original: self.exif_field(DATE_TIME_ORIGINAL),
+            orientation: self.exif_field(ORIENTATION).and_then(|v| v.trim().parse().ok()),
+            exposure_time: rational(EXPOSURE_TIME),
+            f_number: rational(F_NUMBER),
+            iso: self.exif_field(ISO).and_then(|v| v.trim().parse().ok()),
+            focal_length: rational(FOCAL_LENGTH),
+            gps
+        }
+    }
+
+    /// Write all fields of `exif` that are not `None` to the image. Fields
+    /// that are `None` are left unchanged.
+    pub fn set_exif(&mut self, exif: &Exif) -> Result<(), VipsError> {
+        let strings = [
+            (MAKE, &exif.make),
+            (MODEL, &exif.model),
+            (SOFTWARE, &exif.software),
+            (DATE_TIME, &exif.date_time),
+            (DATE_TIME_ORIGINAL, &exif.date_time_original)
+        ];
+        for (name, value) in strings {
+            if let Some(value) = value {
+                self.set_exif_field(name, value)?;
+            }
+        }
+
+        let rationals = [
+            (EXPOSURE_TIME, exif.exposure_time),
+            (F_NUMBER, exif.f_number),
+            (FOCAL_LENGTH, exif.focal_length)
+        ];
+        for (name, value) in rationals {
+            if let Some(value) = value {
+                self.set_exif_field(name, &format_rational(value))?;
+            }
+        }
+
+        if let Some(iso) = exif.iso {
+            self.set_exif_field(ISO, &iso.to_string())?;
+        }
+
+        if let Some(orientation) = exif.orientation {
+            self.set_exif_field(ORIENTATION, &orientation.to_string())?;
+            // savers write the orientation tag from this field
+            self.set("orientation", MetaValue::Int(orientation))?;
+        }
+
+        if let Some(gps) = exif.gps {
+            self.set_exif_field(GPS_LATITUDE, &format_degrees(gps.latitude))?;
+            self.set_exif_field(GPS_LATITUDE_REF, if gps.latitude < 0.0 { "S" } else { "N" })?;
+            self.set_exif_field(GPS_LONGITUDE, &format_degrees(gps.longitude))?;
+            self.set_exif_field(GPS_LONGITUDE_REF, if gps.longitude < 0.0 { "W" } else { "E" })?;
+            if let Some(altitude) = gps.altitude {
+                self.set_exif_field(GPS_ALTITUDE, &format_rational(altitude))?;
+                self.set_exif_field(GPS_ALTITUDE_REF, if altitude < 0.0 { "1" } else { "0" })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rotate and flip the image upright based on its orientation tag. The
+    /// tag is removed from the returned image.
+    pub fn autorot(&self) -> Result<(VipsImage, Autorotation), VipsError> {
+        unsafe {
+            call_operation("autorot", &[("in", self)], |op| {
+                Ok((get_property(op, "out")?, Autorotation {
+                    angle: get_property(op, "angle")?,
+                    flip: get_property(op, "flip")?
+                }))
+            })
+        }
+    }
+}
+
+// =============================================================================
+// === Helpers =================================================================
+// =============================================================================
+
+/// Strip the description Vips appends to parsed values, like
+/// `"1 (Top-left, Short, 1 components, 2 bytes)"`. The value itself may hold
+/// parentheses, so the group is found by matching them back from the end.
+fn raw_exif_value(value: &str) -> &str {
+    let value = value.trim();
+    if !value.ends_with(" bytes)") {
+        return value;
+    }
+
+    let mut depth = 0;
+    for (i, c) in value.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return value[..i].trim();
+        }
+    }
+
+    value
+}
+
+/// Parse space separated rationals like `"52/1 31/1 1234/100"`
+fn parse_rationals(value: &str) -> Option<Vec<f64>> {
+    value.split_whitespace()
+        .map(|component| match component.split_once('/') {
+            Some((numerator, denominator)) => {
+                let numerator: f64 = numerator.parse().ok()?;
+                let denominator: f64 = denominator.parse().ok()?;
+                if denominator == 0.0 {
+                    return None;
+                }
+                Some(numerator / denominator)
+            },
+            None => component.parse().ok()
+        })
+        .collect()
+}
+
+/// Parse degrees, minutes and seconds into decimal degrees
+fn parse_degrees(value: &str) -> Option<f64> {
+    let components = parse_rationals(value)?;
+    let degrees = components.first()?;
+    let minutes = components.get(1).unwrap_or(&0.0);
+    let seconds = components.get(2).unwrap_or(&0.0);
+
+    Some(degrees + minutes / 60.0 + seconds / 3600.0)
+}
+
+/// Format the magnitude of `value` as a rational with up to six decimals
+fn format_rational(value: f64) -> String {
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    let denominator: u64 = 1_000_000;
+    let numerator = (value.abs() * denominator as f64).round() as u64;
+    let divisor = gcd(numerator, denominator).max(1);
+
+    format!("{}/{}", numerator / divisor, denominator / divisor)
+}
+
+/// Format the magnitude of `value` as degrees, minutes and seconds
+fn format_degrees(value: f64) -> String {
+    let value = value.abs();
+    let degrees = value.trunc();
+    let minutes = ((value - degrees) * 60.0).trunc();
+    let seconds = (value - degrees - minutes / 60.0) * 3600.0;
+
+    format!("{}/1 {}/1 {}", degrees, minutes, format_rational(seconds))
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use vips_sys as s;
+
+    #[test]
+    fn exif_value_parsing() {
+        assert_eq!(raw_exif_value("1 (Top-left, Short, 1 components, 2 bytes)"), "1");
+        assert_eq!(raw_exif_value("Canon"), "Canon");
+        assert_eq!(
+            raw_exif_value("Adobe Photoshop CC (Windows) (Adobe Photoshop CC (Windows), ASCII, 29 components, 29 bytes)"),
+            "Adobe Photoshop CC (Windows)"
+        );
+        assert_eq!(parse_rationals("1/200"), Some(vec![0.005]));
+        assert_eq!(parse_rationals("1/0"), None);
+
+        let degrees = parse_degrees(&format_degrees(-52.520008)).expect("Could not parse degrees");
+        assert!((degrees - 52.520008).abs() < 1e-6);
+        assert_eq!(format_rational(0.005), "1/200");
+    }
+
+    #[test]
+    fn exif_read() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        assert!(img.exif_data().is_some());
+        let exif = img.exif();
+        assert_eq!(exif.orientation, Some(1));
+        assert!(exif.software.is_some());
+        assert_eq!(img.exif_field("exif-ifd0-Orientation").as_deref(), Some("1"));
+
+        // missing fields must not leave messages in the shared error buffer
+        let _errors = crate::error::lock_error_buffer();
+        assert_eq!(img.exif_field("exif-ifd0-NoSuchField"), None);
+        assert!(unsafe { std::ffi::CStr::from_ptr(s::vips_error_buffer()) }.to_bytes().is_empty());
+    }
+
+    #[test]
+    fn exif_roundtrip() {
+        ensure_vips_init_or_exit();
+
+        let mut img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        img.set_exif(&Exif {
+            software: Some("vips-rs".to_string()),
+            ..Default::default()
+        }).expect("Could not set EXIF fields");
+
+        let saved = img.jpegsave_buffer(&JpegSaveOptions::default())
+            .expect("Could not save image");
+        let reloaded = VipsImage::new_from_buffer(&saved, &LoadOptions::default())
+            .expect("Could not load saved image");
+        assert_eq!(reloaded.exif().software.as_deref(), Some("vips-rs"));
+    }
+
+    #[test]
+    fn autorotate() {
+        ensure_vips_init_or_exit();
+
+        let mut img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        let (upright, rotation) = img.autorot()
+            .expect("Could not autorotate image");
+        assert_eq!(rotation, Autorotation { angle: Angle::D0, flip: false });
+        assert_eq!((upright.width(), upright.height()), (img.width(), img.height()));
+
+        img.set_exif(&Exif { orientation: Some(6), ..Default::default() })
+            .expect("Could not set orientation");
+        let (upright, rotation) = img.autorot()
+            .expect("Could not autorotate image");
+        assert_eq!(rotation, Autorotation { angle: Angle::D90, flip: false });
+        assert_eq!((upright.width(), upright.height()), (img.height(), img.width()));
+        assert_eq!(upright.orientation(), 1);
+    }
+}
@@ -0,0 +1,1532 @@
+//! Loading and saving images in the file formats supported by Vips
+
+use std::ffi::{CStr, CString, c_char, c_void};
+use std::path::PathBuf;
+
+use vips_sys as s;
+
+use crate::*;
+use crate::operator::{set_argument, get_property};
+
+// =============================================================================
+// === LoadOptions =============================================================
+// =============================================================================
+
+/// Options for the generic loaders, i.e. `VipsImage::new_from_file_with_options`,
+/// `VipsImage::new_from_buffer` and `VipsImage::new_from_source`.
+///
+/// The loader is picked based on the input, so not every option is supported
+/// in every case: `page` and `n` only apply to multi-page formats, `shrink`
+/// only to JPEG and WebP. Setting an option the picked loader does not know
+/// makes the load fail.
+///
+/// ```ignore
+/// let options = LoadOptions::new()
+///     .access(Access::Sequential)
+///     .shrink(2);
+/// let img = VipsImage::new_from_file_with_options(PathBuf::from("big.jpg"), &options)?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LoadOptions {
+    access: Option<Access>,
+    fail_on: Option<FailOn>,
+    memory: Option<bool>,
+    page: Option<i32>,
+    n: Option<i32>,
+    shrink: Option<i32>,
+    option_string: Option<String>
+}
+
+impl LoadOptions {
+    pub fn new() -> LoadOptions {
+        LoadOptions::default()
+    }
+
+    /// How the pixels will be read. `Access::Sequential` lets Vips stream
+    /// the image top-to-bottom instead of decoding it up front.
+    pub fn access(mut self, access: Access) -> LoadOptions {
+        self.access = Some(access);
+        self
+    }
+
+    /// The kind of problem with the input that should make the load fail.
+    pub fn fail_on(mut self, fail_on: FailOn) -> LoadOptions {
+        self.fail_on = Some(fail_on);
+        self
+    }
+
+    /// Force the image to be decoded to memory rather than to a temporary
+    /// file on disk.
+    pub fn memory(mut self, memory: bool) -> LoadOptions {
+        self.memory = Some(memory);
+        self
+    }
+
+    /// The first page to load from a multi-page file.
+    pub fn page(mut self, page: i32) -> LoadOptions {
+        self.page = Some(page);
+        self
+    }
+
+    /// The number of pages to load from a multi-page file, `-1` for all.
+    pub fn n(mut self, n: i32) -> LoadOptions {
+        self.n = Some(n);
+        self
+    }
+
+    /// Shrink the image by this integer factor while loading.
+    pub fn shrink(mut self, shrink: i32) -> LoadOptions {
+        self.shrink = Some(shrink);
+        self
+    }
+
+    /// Options in the Vips option string syntax, e.g. `"shrink=2,autorotate"`.
+    /// They are applied after all other options.
+    pub fn option_string<T: Into<String>>(mut self, option_string: T) -> LoadOptions {
+        self.option_string = Some(option_string.into());
+        self
+    }
+}
+
+impl ForeignOptions for LoadOptions {
+    unsafe fn apply(&self, op: *mut s::VipsOperation) -> Result<(), VipsError> {
+        set_argument(op, "access", &self.access)?;
+        set_argument(op, "fail_on", &self.fail_on)?;
+        set_argument(op, "memory", &self.memory)?;
+        set_argument(op, "page", &self.page)?;
+        set_argument(op, "n", &self.n)?;
+        set_argument(op, "shrink", &self.shrink)?;
+
+        if let Some(option_string) = &self.option_string {
+            set_option_string(op, option_string)?;
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// === LoadInfo ================================================================
+// =============================================================================
+
+/// Information about how an image was loaded, returned by the format-specific
+/// loaders like `VipsImage::jpegload`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadInfo {
+    /// Nickname of the loader that was used, e.g. `"jpegload_buffer"`
+    pub loader: String,
+    /// The flags the loader reported for the input
+    pub flags: ForeignFlags
+}
+
+impl LoadInfo {
+    /// Whether the image can be read in sections without decoding all of it
+    pub fn is_partial(&self) -> bool {
+        self.flags.contains(ForeignFlags::PARTIAL)
+    }
+
+    /// Whether the image can be read efficiently top-to-bottom
+    pub fn is_sequential(&self) -> bool {
+        self.flags.contains(ForeignFlags::SEQUENTIAL)
+    }
+
+    /// Whether the pixel data is stored in big-endian byte order
+    pub fn is_bigendian(&self) -> bool {
+        self.flags.contains(ForeignFlags::BIGENDIAN)
+    }
+}
+
+impl VipsImage {
+    /// Nickname of the loader this image was loaded with, if any, as recorded
+    /// in its `vips-loader` metadata field
+    pub fn loader(&self) -> Option<String> {
+        let name_c_str = CString::new("vips-loader").ok()?;
+
+        unsafe {
+            // check first, since a missing field would leave a message in the
+            // error buffer
+            if s::vips_image_get_typeof(self.ptr, name_c_str.as_ptr()) == 0 {
+                return None;
+            }
+
+            let mut loader: *const c_char = std::ptr::null();
+            if s::vips_image_get_string(self.ptr, name_c_str.as_ptr(), &mut loader) != 0 {
+                return None;
+            }
+
+            Some(CStr::from_ptr(loader).to_string_lossy().into_owned())
+        }
+    }
+}
+
+// =============================================================================
+// === Format detection ========================================================
+// =============================================================================
+
+/// Description of a load operation compiled into the linked Vips
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loader {
+    /// Nickname of the operation, e.g. `"jpegload_buffer"`
+    pub nickname: String,
+    /// Human-readable description, e.g. `"load jpeg from buffer"`
+    pub description: String,
+    /// File suffixes the loader is used for, e.g. `[".jpg", ".jpeg"]`
+    pub suffixes: Vec<String>,
+    /// The flags for a specific file. Only known for loaders found with
+    /// `find_load`.
+    pub flags: Option<ForeignFlags>
+}
+
+/// Description of a save operation compiled into the linked Vips
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Saver {
+    /// Nickname of the operation, e.g. `"jpegsave_target"`
+    pub nickname: String,
+    /// Human-readable description, e.g. `"save image to jpeg target"`
+    pub description: String,
+    /// File suffixes the saver is used for, e.g. `[".jpg", ".jpeg"]`
+    pub suffixes: Vec<String>
+}
+
+/// Read nickname, description and suffixes from a `VipsForeignClass`
+unsafe fn describe_foreign_class(class: *const s::VipsForeignClass) -> (String, String, Vec<String>) {
+    let object_class = class as *const s::VipsObjectClass;
+
+    let to_string = |p: *const c_char| if p.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(p).to_string_lossy().into_owned()
+    };
+
+    let mut suffixes = Vec::new();
+    let mut suff = (*class).suffs;
+    if !suff.is_null() {
+        while !(*suff).is_null() {
+            suffixes.push(to_string(*suff));
+            suff = suff.add(1);
+        }
+    }
+
+    (
+        to_string((*object_class).nickname),
+        to_string((*object_class).description),
+        suffixes
+    )
+}
+
+/// Look up the class of the operation `nickname`, a subclass of `basename`
+unsafe fn find_foreign_class(basename: &str, nickname: *const c_char) -> Result<*const s::VipsForeignClass, VipsError> {
+    let basename_c_str = CString::new(basename)?;
+    let class = s::vips_class_find(basename_c_str.as_ptr(), nickname);
+    if class.is_null() {
+        return Err(VipsError::new_from_vips_state());
+    }
+    Ok(class as *const s::VipsForeignClass)
+}
+
+impl Loader {
+    unsafe fn from_nickname(nickname: *const c_char) -> Result<Loader, VipsError> {
+        let class = find_foreign_class("VipsForeignLoad", nickname)?;
+        let (nickname, description, suffixes) = describe_foreign_class(class);
+        Ok(Loader { nickname, description, suffixes, flags: None })
+    }
+}
+
+impl Saver {
+    unsafe fn from_nickname(nickname: *const c_char) -> Result<Saver, VipsError> {
+        let class = find_foreign_class("VipsForeignSave", nickname)?;
+        let (nickname, description, suffixes) = describe_foreign_class(class);
+        Ok(Saver { nickname, description, suffixes })
+    }
+}
+
+/// Find the loader Vips would use for `file`, based on the file's contents.
+///
+/// Fails if the file cannot be read or is not in a supported format.
+pub fn find_load(file: PathBuf) -> Result<Loader, VipsError> {
+    let path_str = match file.to_str() {
+        Some(pstr) => pstr,
+        None => return Err(VipsError::new("Could not convert path to string"))
+    };
+    let filename_c_str = CString::new(split_option_string(path_str).0)?;
+
+    unsafe {
+        let loader = s::vips_foreign_find_load(filename_c_str.as_ptr());
+        if loader.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        let mut description = Loader::from_nickname(loader)?;
+        description.flags = Some(ForeignFlags::from_raw(
+            s::vips_foreign_flags(loader, filename_c_str.as_ptr())
+        ));
+        Ok(description)
+    }
+}
+
+/// Find the loader Vips would use for the formatted image held in `data`.
+///
+/// Fails if the data is not in a supported format.
+pub fn find_load_buffer(data: &[u8]) -> Result<Loader, VipsError> {
+    unsafe {
+        let loader = s::vips_foreign_find_load_buffer(
+            data.as_ptr() as *const c_void, data.len()
+        );
+        if loader.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        Loader::from_nickname(loader)
+    }
+}
+
+/// Find the loader Vips would use for `source`. Only the start of the source
+/// is read, so it can still be passed on to a loader afterwards.
+///
+/// Fails if the data is not in a supported format.
+pub fn find_load_source(source: &VipsSource) -> Result<Loader, VipsError> {
+    unsafe {
+        let loader = s::vips_foreign_find_load_source(source.ptr);
+        if loader.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        Loader::from_nickname(loader)
+    }
+}
+
+/// Find the saver Vips would use for writing to `file`, based on its suffix.
+/// A bare suffix like `".png"` works too.
+pub fn find_save(file: PathBuf) -> Result<Saver, VipsError> {
+    let path_str = match file.to_str() {
+        Some(pstr) => pstr,
+        None => return Err(VipsError::new("Could not convert path to string"))
+    };
+    let filename_c_str = CString::new(path_str)?;
+
+    unsafe {
+        let saver = s::vips_foreign_find_save(filename_c_str.as_ptr());
+        if saver.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        Saver::from_nickname(saver)
+    }
+}
+
+/// Find the saver Vips would use for encoding to memory in the format given by
+/// `suffix`, e.g. `".webp"`.
+pub fn find_save_buffer(suffix: &str) -> Result<Saver, VipsError> {
+    let suffix_c_str = CString::new(suffix)?;
+
+    unsafe {
+        let saver = s::vips_foreign_find_save_buffer(suffix_c_str.as_ptr());
+        if saver.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        Saver::from_nickname(saver)
+    }
+}
+
+/// Find the saver Vips would use for writing to a `VipsTarget` in the format
+/// given by `suffix`, e.g. `".tif"`.
+pub fn find_save_target(suffix: &str) -> Result<Saver, VipsError> {
+    let suffix_c_str = CString::new(suffix)?;
+
+    unsafe {
+        let saver = s::vips_foreign_find_save_target(suffix_c_str.as_ptr());
+        if saver.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        Saver::from_nickname(saver)
+    }
+}
+
+unsafe extern "C" fn collect_foreign_class_cb(
+    class: *mut c_void,
+    classes: *mut c_void,
+    _b: *mut c_void
+) -> *mut c_void {
+    let classes = &mut *(classes as *mut Vec<*const s::VipsForeignClass>);
+    classes.push(class as *const s::VipsForeignClass);
+
+    // returning non-null would stop the iteration
+    std::ptr::null_mut()
+}
+
+/// List the classes of all non-abstract subclasses of `basename`
+fn foreign_classes(basename: &str) -> Vec<*const s::VipsForeignClass> {
+    let mut classes: Vec<*const s::VipsForeignClass> = Vec::new();
+
+    if let Ok(basename_c_str) = CString::new(basename) {
+        unsafe {
+            s::vips_foreign_map(
+                basename_c_str.as_ptr(),
+                Some(collect_foreign_class_cb),
+                &mut classes as *mut Vec<*const s::VipsForeignClass> as *mut c_void,
+                std::ptr::null_mut()
+            );
+        }
+    }
+
+    classes
+}
+
+/// List all loaders compiled into the linked Vips, in the order Vips tries
+/// them in
+pub fn loaders() -> Vec<Loader> {
+    foreign_classes("VipsForeignLoad").into_iter()
+        .map(|class| {
+            let (nickname, description, suffixes) = unsafe { describe_foreign_class(class) };
+            Loader { nickname, description, suffixes, flags: None }
+        })
+        .collect()
+}
+
+/// List all savers compiled into the linked Vips, in the order Vips tries
+/// them in
+pub fn savers() -> Vec<Saver> {
+    foreign_classes("VipsForeignSave").into_iter()
+        .map(|class| {
+            let (nickname, description, suffixes) = unsafe { describe_foreign_class(class) };
+            Saver { nickname, description, suffixes }
+        })
+        .collect()
+}
+
+// =============================================================================
+// === Helpers =================================================================
+// =============================================================================
+
+/// Options structs for loaders and savers
+pub(crate) trait ForeignOptions {
+    /// Set all options which have a value as arguments of `op`
+    unsafe fn apply(&self, op: *mut s::VipsOperation) -> Result<(), VipsError>;
+}
+
+/// Split a filename with embedded options like `"file.jpg[shrink=2]"` into the
+/// filename and the option string (without brackets).
+pub(crate) fn split_option_string(filename: &str) -> (&str, &str) {
+    if let Some(stripped) = filename.strip_suffix(']') {
+        if let Some(start) = stripped.rfind('[') {
+            return (&stripped[..start], &stripped[start + 1..]);
+        }
+    }
+
+    (filename, "")
+}
+
+/// Apply options in the Vips option string syntax to `op`.
+pub(crate) unsafe fn set_option_string(
+    op: *mut s::VipsOperation,
+    option_string: &str
+) -> Result<(), VipsError> {
+    if option_string.is_empty() {
+        return Ok(());
+    }
+
+    let options_c_str = CString::new(option_string)?;
+    if s::vips_object_set_from_string(
+        op as *mut s::VipsObject,
+        options_c_str.as_ptr()
+    ) != 0 {
+        return Err(VipsError::new_from_vips_state());
+    }
+
+    Ok(())
+}
+
+/// Run the load operation `loader`, passing `input` as its `input_name`
+/// argument and applying `options` and the extra `option_string`.
+pub(crate) unsafe fn load(
+    loader: *const c_char,
+    input_name: &str,
+    input: &dyn ToGValue,
+    options: &dyn ForeignOptions,
+    option_string: &str
+) -> Result<(VipsImage, LoadInfo), VipsError> {
+    let loader_name = CStr::from_ptr(loader).to_string_lossy().into_owned();
+    let op = s::vips_operation_new(loader);
+    if op.is_null() {
+        return Err(VipsError::new("Could not create operation").with_operation(loader_name));
+    }
+
+    let setup = || -> Result<(), VipsError> {
+        set_argument(op, input_name, input)?;
+        set_option_string(op, option_string)?;
+        options.apply(op)
+    };
+
+    if let Err(e) = setup() {
+        s::g_object_unref(op as *mut c_void);
+        return Err(e.with_operation(loader_name));
+    }
+
+    let built_op = s::vips_cache_operation_build(op);
+    s::g_object_unref(op as *mut c_void);
+    if built_op.is_null() {
+        return Err(VipsError::new_from_vips_state()
+            .with_default_kind(ErrorKind::LoadFailed)
+            .with_operation(loader_name));
+    }
+
+    let out = get_property::<VipsImage>(built_op as *mut s::GObject, "out")
+        .and_then(|image| {
+            let flags = get_property(built_op as *mut s::GObject, "flags")?;
+            let info = LoadInfo {
+                loader: image.loader().unwrap_or_default(),
+                flags
+            };
+            Ok((image, info))
+        });
+
+    s::vips_object_unref_outputs(built_op as *mut s::VipsObject);
+    s::g_object_unref(built_op as *mut c_void);
+
+    out
+}
+
+/// Run the save operation `saver` on `image`, passing `output` as the named
+/// output argument (e.g. `"filename"` or `"target"`) and applying `options`.
+///
+/// Savers writing to memory do not take an output argument. For these, pass
+/// `None` as `output` and the encoded data is returned.
+pub(crate) unsafe fn save(
+    saver: &str,
+    image: &VipsImage,
+    output: Option<(&str, &dyn ToGValue)>,
+    options: &dyn ForeignOptions
+) -> Result<Option<Vec<u8>>, VipsError> {
+    let saver_c_str = CString::new(saver)?;
+    let op = s::vips_operation_new(saver_c_str.as_ptr());
+    if op.is_null() {
+        return Err(VipsError::new_from_vips_state().with_operation(saver));
+    }
+
+    let setup = || -> Result<(), VipsError> {
+        set_argument(op, "in", image)?;
+        if let Some((output_name, output_value)) = output {
+            set_argument(op, output_name, output_value)?;
+        }
+        options.apply(op)
+    };
+
+    if let Err(e) = setup() {
+        s::g_object_unref(op as *mut c_void);
+        return Err(e.with_operation(saver));
+    }
+
+    let built_op = s::vips_cache_operation_build(op);
+    s::g_object_unref(op as *mut c_void);
+    if built_op.is_null() {
+        return Err(VipsError::new_from_vips_state().with_operation(saver));
+    }
+
+    let buffer = match output {
+        Some(_) => Ok(None),
+        None => get_property(built_op as *mut s::GObject, "buffer").map(Some)
+    };
+
+    s::vips_object_unref_outputs(built_op as *mut s::VipsObject);
+    s::g_object_unref(built_op as *mut c_void);
+
+    buffer
+}
+
+/// Define an options struct for a family of savers and the methods on
+/// `VipsImage` to call them, like:
+///
+/// ```ignore
+/// define_saver!(
+///     /// Options for saving JPEG files
+///     JpegSaveOptions {
+///         /// Quality factor
+///         q: i32 => "Q"
+///     },
+///     file: jpegsave,
+///     buffer: jpegsave_buffer,
+///     target: jpegsave_target
+/// );
+/// ```
+///
+/// Each field maps to the saver argument given after the `=>` and is wrapped in
+/// an `Option` so unset fields fall back to the Vips defaults. The method names
+/// must be the nicknames of the save operations. Variants a format does not
+/// support can be left out.
+macro_rules! define_saver {
+    (
+        $(#[$meta:meta])*
+        $options:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $field_type:ty => $prop_name:literal
+            ),* $(,)?
+        }
+        $(, file: $file_saver:ident)?
+        $(, buffer: $buffer_saver:ident)?
+        $(, target: $target_saver:ident)?
+        $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default)]
+        pub struct $options {
+            $(
+                $(#[$field_meta])*
+                pub $field: Option<$field_type>
+            ),*
+        }
+
+        impl ForeignOptions for $options {
+            unsafe fn apply(&self, op: *mut s::VipsOperation) -> Result<(), VipsError> {
+                $( set_argument(op, $prop_name, &self.$field)?; )*
+                Ok(())
+            }
+        }
+
+        impl VipsImage {
+            $(
+                pub fn $file_saver(&self, file: PathBuf, options: &$options) -> Result<(), VipsError> {
+                    let filename = match file.to_str() {
+                        Some(filename) => filename,
+                        None => return Err(VipsError::new("Could not convert path to string"))
+                    };
+
+                    unsafe {
+                        save(stringify!($file_saver), self, Some(("filename", &filename)), options)?;
+                    }
+                    Ok(())
+                }
+            )?
+
+            $(
+                pub fn $buffer_saver(&self, options: &$options) -> Result<Vec<u8>, VipsError> {
+                    match unsafe { save(stringify!($buffer_saver), self, None, options)? } {
+                        Some(buffer) => Ok(buffer),
+                        None => Err(VipsError::new("Saver did not return a buffer"))
+                    }
+                }
+            )?
+
+            $(
+                pub fn $target_saver(&self, target: &VipsTarget, options: &$options) -> Result<(), VipsError> {
+                    unsafe {
+                        save(stringify!($target_saver), self, Some(("target", target)), options)?;
+                    }
+                    Ok(())
+                }
+            )?
+        }
+    };
+}
+
+/// Define an options struct for a family of loaders and the associated
+/// functions on `VipsImage` to call them, like:
+///
+/// ```ignore
+/// define_loader!(
+///     /// Options for loading JPEG files
+///     JpegLoadOptions {
+///         /// Shrink factor on load
+///         shrink: i32 => "shrink"
+///     },
+///     file: jpegload,
+///     buffer: jpegload_buffer,
+///     source: jpegload_source
+/// );
+/// ```
+///
+/// Besides the listed fields, every struct gets the `access`, `fail_on` and
+/// `memory` options shared by all loaders. The rules for fields and variants
+/// are the same as for `define_saver!`.
+macro_rules! define_loader {
+    (
+        $(#[$meta:meta])*
+        $options:ident {
+            $(
+                $(#[$field_meta:meta])*
+                $field:ident: $field_type:ty => $prop_name:literal
+            ),* $(,)?
+        }
+        $(, file: $file_loader:ident)?
+        $(, buffer: $buffer_loader:ident)?
+        $(, source: $source_loader:ident)?
+        $(,)?
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default)]
+        pub struct $options {
+            /// How the pixels will be read
+            pub access: Option<Access>,
+            /// The kind of problem with the input that should make the load fail
+            pub fail_on: Option<FailOn>,
+            /// Force the image to be decoded to memory
+            pub memory: Option<bool>,
+            $(
+                $(#[$field_meta])*
+                pub $field: Option<$field_type>
+            ),*
+        }
+
+        impl ForeignOptions for $options {
+            unsafe fn apply(&self, op: *mut s::VipsOperation) -> Result<(), VipsError> {
+                set_argument(op, "access", &self.access)?;
+                set_argument(op, "fail_on", &self.fail_on)?;
+                set_argument(op, "memory", &self.memory)?;
+                $( set_argument(op, $prop_name, &self.$field)?; )*
+                Ok(())
+            }
+        }
+
+        impl VipsImage {
+            $(
+                pub fn $file_loader(file: PathBuf, options: &$options) -> Result<(VipsImage, LoadInfo), VipsError> {
+                    let filename = match file.to_str() {
+                        Some(filename) => filename,
+                        None => return Err(VipsError::new("Could not convert path to string"))
+                    };
+                    let loader_c_str = CString::new(stringify!($file_loader))?;
+
+                    unsafe { load(loader_c_str.as_ptr(), "filename", &filename, options, "") }
+                }
+            )?
+
+            $(
+                pub fn $buffer_loader(data: &[u8], options: &$options) -> Result<(VipsImage, LoadInfo), VipsError> {
+                    let loader_c_str = CString::new(stringify!($buffer_loader))?;
+
+                    unsafe { load(loader_c_str.as_ptr(), "buffer", &data, options, "") }
+                }
+            )?
+
+            $(
+                pub fn $source_loader(source: &VipsSource, options: &$options) -> Result<(VipsImage, LoadInfo), VipsError> {
+                    let loader_c_str = CString::new(stringify!($source_loader))?;
+
+                    unsafe { load(loader_c_str.as_ptr(), "source", source, options, "") }
+                }
+            )?
+        }
+    };
+}
+
+// =============================================================================
+// === Savers ==================================================================
+// =============================================================================
+
+define_saver!(
+    /// Options for `jpegsave`, `jpegsave_buffer` and `jpegsave_target`
+    JpegSaveOptions {
+        /// Quality factor, 1 - 100
+        q: i32 => "Q",
+        /// Remove all metadata from the image
+        strip: bool => "strip",
+        /// Compute optimal Huffman coding tables
+        optimize_coding: bool => "optimize_coding",
+        /// Write a progressive JPEG
+        interlace: bool => "interlace",
+        /// Apply trellis quantisation to each 8x8 block
+        trellis_quant: bool => "trellis_quant",
+        /// Overshoot samples with extreme values
+        overshoot_deringing: bool => "overshoot_deringing",
+        /// Split the spectrum of DCT coefficients into separate scans
+        optimize_scans: bool => "optimize_scans",
+        /// Quantization table to use, 0 - 8
+        quant_table: i32 => "quant_table",
+        /// Chroma subsampling mode
+        subsample_mode: ForeignSubsample => "subsample_mode",
+        /// Add restart markers every this many MCU rows
+        restart_interval: i32 => "restart_interval"
+    },
+    file: jpegsave,
+    buffer: jpegsave_buffer,
+    target: jpegsave_target
+);
+
+define_saver!(
+    /// Options for `pngsave`, `pngsave_buffer` and `pngsave_target`
+    PngSaveOptions {
+        /// Compression level, 0 - 9
+        compression: i32 => "compression",
+        /// Write an interlaced (Adam7) PNG
+        interlace: bool => "interlace",
+        /// Remove all metadata from the image
+        strip: bool => "strip",
+        /// Row filters to try
+        filter: ForeignPngFilter => "filter",
+        /// Quantise to an 8-bit palette
+        palette: bool => "palette",
+        /// Quality factor for palette quantisation, 0 - 100
+        q: i32 => "Q",
+        /// Amount of dithering for palette quantisation, 0 - 1
+        dither: f64 => "dither",
+        /// Bits per sample, 1, 2, 4, 8 or 16
+        bitdepth: i32 => "bitdepth",
+        /// CPU effort spent on quantisation, 1 - 10
+        effort: i32 => "effort"
+    },
+    file: pngsave,
+    buffer: pngsave_buffer,
+    target: pngsave_target
+);
+
+define_saver!(
+    /// Options for `webpsave`, `webpsave_buffer` and `webpsave_target`
+    WebpSaveOptions {
+        /// Quality factor, 0 - 100
+        q: i32 => "Q",
+        /// Use lossless compression
+        lossless: bool => "lossless",
+        /// Preset tuning the encoder for the kind of image
+        preset: WebpPreset => "preset",
+        /// Use high quality chroma subsampling
+        smart_subsample: bool => "smart_subsample",
+        /// Use preprocessing in lossless mode (uses `q`)
+        near_lossless: bool => "near_lossless",
+        /// Quality of the alpha channel, 0 - 100
+        alpha_q: i32 => "alpha_q",
+        /// Optimise animations for size
+        min_size: bool => "min_size",
+        /// CPU effort spent on compression, 0 - 6
+        effort: i32 => "effort",
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: webpsave,
+    buffer: webpsave_buffer,
+    target: webpsave_target
+);
+
+define_saver!(
+    /// Options for `tiffsave`, `tiffsave_buffer` and `tiffsave_target`
+    TiffSaveOptions {
+        /// Compression scheme
+        compression: TiffCompression => "compression",
+        /// Quality factor for JPEG and WebP compression, 1 - 100
+        q: i32 => "Q",
+        /// Predictor for LZW, deflate and zstd compression
+        predictor: TiffPredictor => "predictor",
+        /// Write a tiled TIFF
+        tile: bool => "tile",
+        /// Tile width in pixels
+        tile_width: i32 => "tile_width",
+        /// Tile height in pixels
+        tile_height: i32 => "tile_height",
+        /// Write a pyramidal TIFF
+        pyramid: bool => "pyramid",
+        /// Write pyramid layers as sub-IFDs
+        subifd: bool => "subifd",
+        /// Bits per sample for 1-band images, 1, 2, 4 or 8
+        bitdepth: i32 => "bitdepth",
+        /// Write 1-bit images as MINISWHITE
+        miniswhite: bool => "miniswhite",
+        /// Write a BigTIFF file
+        bigtiff: bool => "bigtiff",
+        /// Use lossless compression for JP2K and WebP compression
+        lossless: bool => "lossless",
+        /// Horizontal resolution in pixels per mm
+        xres: f64 => "xres",
+        /// Vertical resolution in pixels per mm
+        yres: f64 => "yres",
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: tiffsave,
+    buffer: tiffsave_buffer,
+    target: tiffsave_target
+);
+
+define_saver!(
+    /// Options for `heifsave`, `heifsave_buffer` and `heifsave_target`
+    HeifSaveOptions {
+        /// Quality factor, 1 - 100
+        q: i32 => "Q",
+        /// Bits per sample, 8, 10 or 12
+        bitdepth: i32 => "bitdepth",
+        /// Use lossless compression
+        lossless: bool => "lossless",
+        /// Compression format, e.g. HEVC or AV1
+        compression: HeifCompression => "compression",
+        /// CPU effort spent on compression, 0 - 9
+        effort: i32 => "effort",
+        /// Chroma subsampling mode
+        subsample_mode: ForeignSubsample => "subsample_mode",
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: heifsave,
+    buffer: heifsave_buffer,
+    target: heifsave_target
+);
+
+define_saver!(
+    /// Options for `gifsave`, `gifsave_buffer` and `gifsave_target`
+    GifSaveOptions {
+        /// Amount of dithering, 0 - 1
+        dither: f64 => "dither",
+        /// CPU effort spent on quantisation, 1 - 10
+        effort: i32 => "effort",
+        /// Bits per pixel, 1 - 8
+        bitdepth: i32 => "bitdepth",
+        /// Maximum inter-frame error for transparency, 0 - 32
+        interframe_maxerror: f64 => "interframe_maxerror",
+        /// Reoptimise the colour palette for each frame
+        reoptimise: bool => "reoptimise",
+        /// Maximum inter-palette error for palette reuse, 0 - 256
+        interpalette_maxerror: f64 => "interpalette_maxerror",
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: gifsave,
+    buffer: gifsave_buffer,
+    target: gifsave_target
+);
+
+define_saver!(
+    /// Options for `vipssave` and `vipssave_target`, writing the Vips native
+    /// format (`.v`). The format stores any band format without loss, which
+    /// makes it a good fit for intermediate results.
+    VipsSaveOptions {
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: vipssave,
+    target: vipssave_target
+);
+
+define_saver!(
+    /// Options for `rawsave`, writing the pixels without any header
+    RawSaveOptions {
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: rawsave
+);
+
+define_saver!(
+    /// Options for `matrixsave` and `matrixsave_target`, writing 1-band images
+    /// as Vips matrix text files, e.g. convolution masks
+    MatrixSaveOptions {
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: matrixsave,
+    target: matrixsave_target
+);
+
+define_saver!(
+    /// Options for `csvsave` and `csvsave_target`, writing 1-band images as
+    /// comma-separated values
+    CsvSaveOptions {
+        /// Separator between values in a line
+        separator: String => "separator",
+        /// Remove all metadata from the image
+        strip: bool => "strip"
+    },
+    file: csvsave,
+    target: csvsave_target
+);
+
+// =============================================================================
+// === Generic loaders =========================================================
+// =============================================================================
+
+impl VipsImage {
+    /// Load an image from `file`, picking the loader based on the file's
+    /// contents.
+    ///
+    /// Loader options can be given in `options` or appended to the filename in
+    /// the Vips option string syntax, e.g. `"photo.jpg[shrink=2]"`.
+    pub fn new_from_file_with_options(file: PathBuf, options: &LoadOptions) -> Result<VipsImage, VipsError> {
+        let path_str = match file.to_str() {
+            Some(pstr) => pstr,
+            None => return Err(VipsError::new("Could not convert path to string"))
+        };
+        let (filename, option_string) = split_option_string(path_str);
+        let filename_c_str = CString::new(filename)?;
+
+        unsafe {
+            let loader = s::vips_foreign_find_load(filename_c_str.as_ptr());
+            if loader.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            load(loader, "filename", &filename, options, option_string)
+                .map(|(image, _)| image)
+        }
+    }
+
+    /// Load an image from a buffer holding a formatted image file, e.g. the
+    /// contents of a JPEG or PNG file. The format is detected from the data.
+    ///
+    /// `data` is copied into a `VipsBlob` owned by the loader, so it does not
+    /// need to outlive the returned image.
+    pub fn new_from_buffer(data: &[u8], options: &LoadOptions) -> Result<VipsImage, VipsError> {
+        unsafe {
+            let loader = s::vips_foreign_find_load_buffer(
+                data.as_ptr() as *const c_void, data.len()
+            );
+            if loader.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            load(loader, "buffer", &data, options, "")
+                .map(|(image, _)| image)
+        }
+    }
+
+    /// Load an image from `source`. The format is detected from the data.
+    pub fn new_from_source(source: &VipsSource, options: &LoadOptions) -> Result<VipsImage, VipsError> {
+        unsafe {
+            let loader = s::vips_foreign_find_load_source(source.ptr);
+            if loader.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            load(loader, "source", source, options, "")
+                .map(|(image, _)| image)
+        }
+    }
+}
+
+// =============================================================================
+// === Loaders =================================================================
+// =============================================================================
+
+define_loader!(
+    /// Options for `jpegload`, `jpegload_buffer` and `jpegload_source`
+    JpegLoadOptions {
+        /// Shrink by this integer factor (1, 2, 4 or 8) while loading
+        shrink: i32 => "shrink",
+        /// Rotate the image upright based on its EXIF orientation
+        autorotate: bool => "autorotate"
+    },
+    file: jpegload,
+    buffer: jpegload_buffer,
+    source: jpegload_source
+);
+
+define_loader!(
+    /// Options for `pngload`, `pngload_buffer` and `pngload_source`
+    PngLoadOptions {},
+    file: pngload,
+    buffer: pngload_buffer,
+    source: pngload_source
+);
+
+define_loader!(
+    /// Options for `webpload`, `webpload_buffer` and `webpload_source`
+    WebpLoadOptions {
+        /// First page to load
+        page: i32 => "page",
+        /// Number of pages to load, `-1` for all
+        n: i32 => "n",
+        /// Scale factor applied while loading
+        scale: f64 => "scale"
+    },
+    file: webpload,
+    buffer: webpload_buffer,
+    source: webpload_source
+);
+
+define_loader!(
+    /// Options for `tiffload`, `tiffload_buffer` and `tiffload_source`
+    TiffLoadOptions {
+        /// First page to load
+        page: i32 => "page",
+        /// Number of pages to load, `-1` for all
+        n: i32 => "n",
+        /// Sub-IFD to load from the selected page, `-1` for the main image
+        subifd: i32 => "subifd",
+        /// Rotate the image upright based on its orientation tag
+        autorotate: bool => "autorotate"
+    },
+    file: tiffload,
+    buffer: tiffload_buffer,
+    source: tiffload_source
+);
+
+define_loader!(
+    /// Options for `pdfload`, `pdfload_buffer` and `pdfload_source`
+    PdfLoadOptions {
+        /// First page to load
+        page: i32 => "page",
+        /// Number of pages to load, `-1` for all
+        n: i32 => "n",
+        /// Render at this resolution
+        dpi: f64 => "dpi",
+        /// Scale the output by this factor
+        scale: f64 => "scale"
+    },
+    file: pdfload,
+    buffer: pdfload_buffer,
+    source: pdfload_source
+);
+
+define_loader!(
+    /// Options for `svgload`, `svgload_buffer` and `svgload_source`
+    SvgLoadOptions {
+        /// Render at this resolution
+        dpi: f64 => "dpi",
+        /// Scale the output by this factor
+        scale: f64 => "scale",
+        /// Allow SVGs of any size
+        unlimited: bool => "unlimited"
+    },
+    file: svgload,
+    buffer: svgload_buffer,
+    source: svgload_source
+);
+
+define_loader!(
+    /// Options for `heifload`, `heifload_buffer` and `heifload_source`
+    HeifLoadOptions {
+        /// First page to load
+        page: i32 => "page",
+        /// Number of pages to load, `-1` for all
+        n: i32 => "n",
+        /// Load the embedded thumbnail instead of the full image
+        thumbnail: bool => "thumbnail",
+        /// Remove all denial of service limits
+        unlimited: bool => "unlimited"
+    },
+    file: heifload,
+    buffer: heifload_buffer,
+    source: heifload_source
+);
+
+define_loader!(
+    /// Options for `gifload`, `gifload_buffer` and `gifload_source`
+    GifLoadOptions {
+        /// First page to load
+        page: i32 => "page",
+        /// Number of pages to load, `-1` for all
+        n: i32 => "n"
+    },
+    file: gifload,
+    buffer: gifload_buffer,
+    source: gifload_source
+);
+
+define_loader!(
+    /// Options for `vipsload` and `vipsload_source`, reading the Vips native
+    /// format (`.v`)
+    VipsLoadOptions {},
+    file: vipsload,
+    source: vipsload_source
+);
+
+define_loader!(
+    /// Options for `matrixload` and `matrixload_source`, reading Vips matrix
+    /// text files as 1-band double images
+    MatrixLoadOptions {},
+    file: matrixload,
+    source: matrixload_source
+);
+
+define_loader!(
+    /// Options for `csvload` and `csvload_source`, reading comma-separated
+    /// values as 1-band double images
+    CsvLoadOptions {
+        /// Number of lines to skip at the start of the file
+        skip: i32 => "skip",
+        /// Number of lines to read, `-1` for all
+        lines: i32 => "lines",
+        /// Characters that count as whitespace
+        whitespace: String => "whitespace",
+        /// Characters that separate values
+        separator: String => "separator"
+    },
+    file: csvload,
+    source: csvload_source
+);
+
+define_loader!(
+    /// Options for `rawload`, reading headerless pixel data
+    RawLoadOptions {
+        /// Number of bytes to skip at the start of the file
+        offset: u64 => "offset",
+        /// Band format of the pixels, defaults to 8-bit unsigned
+        format: BandFormat => "format",
+        /// How to interpret the pixels
+        interpretation: Interpretation => "interpretation"
+    }
+);
+
+/// The size `rawload` requires on top of its options
+struct RawLoadArgs<'a> {
+    width: i32,
+    height: i32,
+    bands: i32,
+    options: &'a RawLoadOptions
+}
+
+impl ForeignOptions for RawLoadArgs<'_> {
+    unsafe fn apply(&self, op: *mut s::VipsOperation) -> Result<(), VipsError> {
+        set_argument(op, "width", &self.width)?;
+        set_argument(op, "height", &self.height)?;
+        set_argument(op, "bands", &self.bands)?;
+        self.options.apply(op)
+    }
+}
+
+impl VipsImage {
+    /// Load a headerless file of pixels, as written by `rawsave`. Since the
+    /// file carries no header, the image's size must be given here and its
+    /// band format in `options`.
+    pub fn rawload(
+        file: PathBuf,
+        width: i32,
+        height: i32,
+        bands: i32,
+        options: &RawLoadOptions
+    ) -> Result<(VipsImage, LoadInfo), VipsError> {
+        let filename = match file.to_str() {
+            Some(filename) => filename,
+            None => return Err(VipsError::new("Could not convert path to string"))
+        };
+        let loader_c_str = CString::new("rawload")?;
+        let args = RawLoadArgs { width, height, bands, options };
+
+        unsafe { load(loader_c_str.as_ptr(), "filename", &filename, &args, "") }
+    }
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn option_string_split() {
+        assert_eq!(split_option_string("a.jpg"), ("a.jpg", ""));
+        assert_eq!(split_option_string("a.jpg[shrink=2]"), ("a.jpg", "shrink=2"));
+        assert_eq!(split_option_string("[x]/a.tif[page=1,n=2]"), ("[x]/a.tif", "page=1,n=2"));
+        assert_eq!(split_option_string("a.jpg[]"), ("a.jpg", ""));
+    }
+
+    #[test]
+    fn load_with_options() {
+        ensure_vips_init_or_exit();
+
+        let options = LoadOptions::new()
+            .access(Access::Sequential)
+            .fail_on(FailOn::Error)
+            .shrink(2);
+        let img = VipsImage::new_from_file_with_options(
+            PathBuf::from("./data/test.jpg"),
+            &options
+        ).expect("Image could not be created from file");
+        assert_eq!(unsafe { s::vips_image_get_height(img.ptr) }, 256);
+
+        img.write_to_file(PathBuf::from("./data/test_shrink_on_load.jpg"))
+            .expect("Could not save image to file");
+    }
+
+    #[test]
+    fn load_with_option_string() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg[shrink=4]"))
+            .expect("Image could not be created from file");
+        assert_eq!(unsafe { s::vips_image_get_height(img.ptr) }, 128);
+
+        let data = std::fs::read("./data/test.jpg")
+            .expect("Could not read test file");
+        let img = VipsImage::new_from_buffer(&data, &LoadOptions::new().option_string("shrink=2"))
+            .expect("Image could not be created from buffer");
+        assert_eq!(unsafe { s::vips_image_get_height(img.ptr) }, 256);
+    }
+
+    #[test]
+    fn save_jpeg() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        img.jpegsave(PathBuf::from("./data/test_jpegsave.jpg"), &JpegSaveOptions {
+            q: Some(60),
+            strip: Some(true),
+            optimize_coding: Some(true),
+            ..Default::default()
+        }).expect("Could not save JPEG file");
+
+        let small = img.jpegsave_buffer(&JpegSaveOptions { q: Some(10), ..Default::default() })
+            .expect("Could not save JPEG to buffer");
+        let large = img.jpegsave_buffer(&JpegSaveOptions { q: Some(100), ..Default::default() })
+            .expect("Could not save JPEG to buffer");
+        assert_eq!(&small[..2], b"\xff\xd8");
+        assert!(small.len() < large.len());
+    }
+
+    #[test]
+    fn save_png_webp_gif() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let png = img.pngsave_buffer(&PngSaveOptions {
+            compression: Some(9),
+            interlace: Some(true),
+            ..Default::default()
+        }).expect("Could not save PNG to buffer");
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let webp = img.webpsave_buffer(&WebpSaveOptions {
+            lossless: Some(true),
+            effort: Some(2),
+            ..Default::default()
+        }).expect("Could not save WebP to buffer");
+        assert_eq!(&webp[..4], b"RIFF");
+
+        let gif = img.gifsave_buffer(&GifSaveOptions::default())
+            .expect("Could not save GIF to buffer");
+        assert_eq!(&gif[..4], b"GIF8");
+    }
+
+    #[test]
+    fn save_tiff_to_target() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let target = VipsTarget::new_to_memory()
+            .expect("Could not create memory target");
+        img.tiffsave_target(&target, &TiffSaveOptions {
+            compression: Some(TiffCompression::Lzw),
+            tile: Some(true),
+            tile_width: Some(128),
+            tile_height: Some(128),
+            pyramid: Some(true),
+            ..Default::default()
+        }).expect("Could not save TIFF to target");
+
+        let tiff = target.bytes().expect("Could not get data from target");
+        assert!(&tiff[..4] == b"II*\0" || &tiff[..4] == b"MM\0*");
+
+        let reloaded = VipsImage::new_from_buffer(&tiff, &LoadOptions::default())
+            .expect("Could not reload saved TIFF");
+        assert_eq!(reloaded.nbands(), img.nbands());
+    }
+
+    #[test]
+    fn save_with_invalid_option() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        match img.jpegsave_buffer(&JpegSaveOptions { q: Some(1000), ..Default::default() }) {
+            Ok(_) => panic!("Passing an out-of-range quality should error"),
+            Err(e) => {
+                assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+                assert_eq!(e.argument(), Some("Q"));
+                assert_eq!(e.operation(), Some("jpegsave_buffer"));
+            }
+        }
+    }
+
+    #[test]
+    fn load_jpeg() {
+        ensure_vips_init_or_exit();
+
+        let (img, info) = VipsImage::jpegload(
+            PathBuf::from("./data/test.jpg"),
+            &JpegLoadOptions { shrink: Some(2), ..Default::default() }
+        ).expect("Could not load JPEG file");
+        assert_eq!(unsafe { s::vips_image_get_height(img.ptr) }, 256);
+        assert_eq!(info.loader, "jpegload");
+        assert!(info.is_sequential());
+
+        let data = std::fs::read("./data/test.jpg")
+            .expect("Could not read test file");
+        let (_, info) = VipsImage::jpegload_buffer(&data, &JpegLoadOptions::default())
+            .expect("Could not load JPEG from buffer");
+        assert_eq!(info.loader, "jpegload_buffer");
+
+        let source = VipsSource::new_from_memory(&data)
+            .expect("Could not create source from memory");
+        let (img, info) = VipsImage::jpegload_source(&source, &JpegLoadOptions {
+            access: Some(Access::Sequential),
+            ..Default::default()
+        }).expect("Could not load JPEG from source");
+        assert_eq!(info.loader, "jpegload_source");
+        assert_eq!(img.loader(), Some(String::from("jpegload_source")));
+    }
+
+    #[test]
+    fn load_forced_format() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        // forcing the wrong loader must fail rather than sniff the format
+        match VipsImage::pngload(PathBuf::from("./data/test.jpg"), &PngLoadOptions::default()) {
+            Ok(_) => panic!("Loading a JPEG file as PNG should error"),
+            Err(_) => {}
+        }
+
+        let png = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file")
+            .pngsave_buffer(&PngSaveOptions::default())
+            .expect("Could not save PNG to buffer");
+        let (img, info) = VipsImage::pngload_buffer(&png, &PngLoadOptions::default())
+            .expect("Could not load PNG from buffer");
+        assert_eq!(info.loader, "pngload_buffer");
+        assert_eq!(img.nbands(), 3);
+    }
+
+    #[test]
+    fn detect_formats() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let loader = find_load(PathBuf::from("./data/test.jpg"))
+            .expect("Could not find loader for JPEG file");
+        assert_eq!(loader.nickname, "jpegload");
+        assert!(loader.suffixes.iter().any(|suffix| suffix == ".jpg"));
+        assert!(loader.flags.is_some());
+
+        let data = std::fs::read("./data/test.jpg")
+            .expect("Could not read test file");
+        let loader = find_load_buffer(&data)
+            .expect("Could not find loader for JPEG buffer");
+        assert_eq!(loader.nickname, "jpegload_buffer");
+
+        let source = VipsSource::new_from_memory(&data)
+            .expect("Could not create source from memory");
+        let loader = find_load_source(&source)
+            .expect("Could not find loader for JPEG source");
+        assert_eq!(loader.nickname, "jpegload_source");
+        // detection must not consume the source
+        VipsImage::new_from_source(&source, &LoadOptions::default())
+            .expect("Image could not be created from source");
+
+        assert_eq!(find_save(PathBuf::from("out.png")).expect("No PNG saver").nickname, "pngsave");
+        assert_eq!(find_save_buffer(".webp").expect("No WebP saver").nickname, "webpsave_buffer");
+        assert_eq!(find_save_target(".tif").expect("No TIFF saver").nickname, "tiffsave_target");
+
+        match find_load_buffer(b"definitely not an image") {
+            Ok(_) => panic!("Detecting an unknown format should error"),
+            Err(_) => {}
+        }
+        match find_save(PathBuf::from("out.not-a-format")) {
+            Ok(_) => panic!("Detecting an unknown format should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn list_formats() {
+        ensure_vips_init_or_exit();
+
+        let loaders = loaders();
+        assert!(loaders.iter().any(|loader| loader.nickname == "jpegload"));
+        assert!(loaders.iter().any(|loader| loader.nickname == "pngload_source"));
+
+        let savers = savers();
+        assert!(savers.iter().any(|saver| saver.nickname == "jpegsave"));
+        assert!(savers.iter().all(|saver| !saver.nickname.is_empty()));
+    }
+
+    #[test]
+    fn vips_format_roundtrip() {
+        ensure_vips_init_or_exit();
+
+        let values: Vec<f32> = (0..6 * 4).map(|v| v as f32 * 0.25 - 2.0).collect();
+        let img = VipsImage::new_from_slice(&values, 6, 4, 1)
+            .expect("Could not create image from slice");
+        img.vipssave(PathBuf::from("./data/test_out.v"), &VipsSaveOptions::default())
+            .expect("Could not save image as .v");
+
+        let (mut loaded, info) = VipsImage::vipsload(
+            PathBuf::from("./data/test_out.v"),
+            &VipsLoadOptions::default()
+        ).expect("Could not load .v image");
+        assert_eq!(info.loader, "vipsload");
+        assert_eq!(loaded.as_slice::<f32>().expect("Could not borrow image data"), &values[..]);
+
+        let rw = VipsImage::new_from_file_rw(PathBuf::from("./data/test_out.v"))
+            .expect("Could not open .v image for writing");
+        assert_eq!((rw.width(), rw.height()), (6, 4));
+    }
+
+    #[test]
+    fn raw_roundtrip() {
+        ensure_vips_init_or_exit();
+
+        let values: Vec<u16> = (0..5 * 3 * 2).map(|v| v * 1000).collect();
+        let img = VipsImage::new_from_slice(&values, 5, 3, 2)
+            .expect("Could not create image from slice");
+        img.rawsave(PathBuf::from("./data/test_out.raw"), &RawSaveOptions::default())
+            .expect("Could not save raw image");
+
+        let (mut loaded, _) = VipsImage::rawload(
+            PathBuf::from("./data/test_out.raw"), 5, 3, 2,
+            &RawLoadOptions {
+                format: Some(BandFormat::UShort),
+                ..Default::default()
+            }
+        ).expect("Could not load raw image");
+        assert_eq!(loaded.as_slice::<u16>().expect("Could not borrow image data"), &values[..]);
+
+        // view the same file as 8-bit data, skipping the first pixel
+        let mut mapped = VipsImage::new_from_file_raw(PathBuf::from("./data/test_out.raw"), 10, 2, 2, 4)
+            .expect("Could not map raw file");
+        assert_eq!(mapped.as_bytes().expect("Could not borrow image data").len(), 10 * 2 * 2);
+    }
+
+    #[test]
+    fn matrix_and_csv_roundtrip() {
+        ensure_vips_init_or_exit();
+
+        let mask = [-1.0, 0.0, 1.0, -2.0, 0.0, 2.0, -1.0, 0.0, 1.0];
+        let img = VipsImage::new_matrix_from_array(3, 3, &mask)
+            .expect("Could not create matrix");
+
+        img.matrixsave(PathBuf::from("./data/test_out.mat"), &MatrixSaveOptions::default())
+            .expect("Could not save matrix");
+        let (mut loaded, _) = VipsImage::matrixload(
+            PathBuf::from("./data/test_out.mat"),
+            &MatrixLoadOptions::default()
+        ).expect("Could not load matrix");
+        assert_eq!(loaded.as_slice::<f64>().expect("Could not borrow matrix data"), &mask);
+
+        img.csvsave(
+            PathBuf::from("./data/test_out.csv"),
+            &CsvSaveOptions { separator: Some(";".to_string()), ..Default::default() }
+        ).expect("Could not save CSV");
+        let csv = std::fs::read_to_string("./data/test_out.csv")
+            .expect("Could not read CSV");
+        assert!(csv.starts_with("-1;0;1"));
+
+        let (mut loaded, _) = VipsImage::csvload(
+            PathBuf::from("./data/test_out.csv"),
+            &CsvLoadOptions { separator: Some(";".to_string()), ..Default::default() }
+        ).expect("Could not load CSV");
+        assert_eq!((loaded.width(), loaded.height()), (3, 3));
+        assert_eq!(loaded.as_slice::<f64>().expect("Could not borrow CSV data"), &mask);
+    }
+
+    #[test]
+    fn load_with_unsupported_option() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        // JPEG files do not have pages
+        match VipsImage::new_from_file_with_options(
+            PathBuf::from("./data/test.jpg"),
+            &LoadOptions::new().page(1)
+        ) {
+            Ok(_) => panic!("Passing an unsupported option should error"),
+            Err(_) => {}
+        }
+    }
+}
@@ -0,0 +1,2 @@
+// Generated by vips-gen. Do not edit by hand, run `cargo run -p vips-gen` to
+// regenerate against the installed libvips.
@@ -1,9 +1,10 @@
-use std::ffi::{CString, c_void};
+use std::ffi::{CStr, CString, c_char, c_void};
 use std::path::PathBuf;
 
 use vips_sys as s;
 
 use crate::*;
+use crate::vips::signal_connect;
 
 // =============================================================================
 // === VipsImage ===============================================================
@@ -63,38 +64,139 @@ impl VipsImage {
     }
 
     // TODO: vips_image_new_memory()
-    // TODO: vips_image_memory()
 
+    /// Load an image from `file`, picking the loader based on the file's
+    /// contents. Loader options can be appended to the filename in the Vips
+    /// option string syntax, e.g. `"photo.jpg[shrink=2]"`.
+    ///
+    /// See `new_from_file_with_options` for typed options.
     pub fn new_from_file(file: PathBuf) -> Result<VipsImage, VipsError> {
-        let path_str = match file.to_str() {
-            Some(pstr) => pstr,
+        VipsImage::new_from_file_with_options(file, &LoadOptions::default())
+    }
+
+    /// Open a file in the Vips native format (`.v`) for reading and writing.
+    /// The file is mapped into memory, so changes to the pixels go straight to
+    /// the file.
+    pub fn new_from_file_rw(file: PathBuf) -> Result<VipsImage, VipsError> {
+        let filename_str = match file.to_str() {
+            Some(filename_str) => filename_str,
             None => return Err(VipsError::new("Could not convert path to string"))
         };
+        let filename_c_str = CString::new(filename_str)?;
+
+        let p = unsafe { s::vips_image_new_from_file_RW(filename_c_str.as_ptr()) };
+        if p.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsImage::from_c_ptr(p)
+    }
 
-        let path_c_str = match CString::new(path_str) {
-            Ok(c_str) => c_str,
-            Err(_) => return Err(VipsError::new("Could not convert path to CString"))
+    /// Map a headerless file of 8-bit pixels into memory. The first `offset`
+    /// bytes of the file are skipped.
+    ///
+    /// See `rawload` for pixel data in other band formats.
+    pub fn new_from_file_raw(
+        file: PathBuf,
+        width: i32,
+        height: i32,
+        bands: i32,
+        offset: u64
+    ) -> Result<VipsImage, VipsError> {
+        let filename_str = match file.to_str() {
+            Some(filename_str) => filename_str,
+            None => return Err(VipsError::new("Could not convert path to string"))
         };
+        let filename_c_str = CString::new(filename_str)?;
 
-        Ok(VipsImage::from_c_ptr(unsafe {
-            vips_sys::vips_image_new_from_file(path_c_str.as_ptr(), 0)
-        })?)
+        let p = unsafe { s::vips_image_new_from_file_raw(
+            filename_c_str.as_ptr(), width, height, bands, offset
+        ) };
+        if p.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsImage::from_c_ptr(p)
     }
 
-    // TODO: vips_image_new_from_file_RW()
-    // TODO: vips_image_new_from_file_raw()
+    /// Create an image from pixels in `data`, copying them. The band format is
+    /// derived from `T`; `data` must hold `width * height * bands` values,
+    /// band-interleaved and in row-major order.
+    pub fn new_from_slice<T: Pixel>(
+        data: &[T],
+        width: i32,
+        height: i32,
+        bands: i32
+    ) -> Result<VipsImage, VipsError> {
+        check_pixel_count(data.len(), width, height, bands)?;
+
+        let p = unsafe { s::vips_image_new_from_memory_copy(
+            data.as_ptr() as *const c_void, std::mem::size_of_val(data),
+            width, height, bands, T::BAND_FORMAT.to_raw()
+        ) };
+        if p.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsImage::from_c_ptr(p)
+    }
 
-    pub fn new_from_memory<'a>(
-        data: &'a [u8],
+    /// Create an image from pixels in `data` without copying them. The image
+    /// takes ownership of `data` and frees it once the image is closed.
+    ///
+    /// See `new_from_slice` for the expected layout.
+    pub fn new_from_vec<T: Pixel>(
+        data: Vec<T>,
+        width: i32,
+        height: i32,
+        bands: i32
+    ) -> Result<VipsImage, VipsError> {
+        check_pixel_count(data.len(), width, height, bands)?;
+
+        unsafe {
+            let p = s::vips_image_new_from_memory(
+                data.as_ptr() as *const c_void, std::mem::size_of_val(&data[..]),
+                width, height, bands, T::BAND_FORMAT.to_raw()
+            );
+            let image = VipsImage::from_c_ptr(p)?;
+
+            // moving the `Vec` into the box leaves its heap buffer in place
+            let data = Box::into_raw(Box::new(data)) as *mut c_void;
+
+            if let Err(e) = signal_connect(
+                image.ptr as *mut c_void,
+                "postclose",
+                std::mem::transmute::<
+                    unsafe extern "C" fn(*mut s::VipsImage, *mut c_void),
+                    unsafe extern "C" fn()
+                >(free_vec_cb::<T>),
+                data,
+                None
+            ) {
+                drop(image);
+                drop(Box::from_raw(data as *mut Vec<T>));
+                return Err(e);
+            }
+
+            Ok(image)
+        }
+    }
+
+    /// Wrap a `VipsImage` around `data` without copying it.
+    ///
+    /// # Safety
+    ///
+    /// The returned image does not keep `data` borrowed. The caller must make
+    /// sure `data` outlives the image and every image derived from it. Prefer
+    /// `new_from_vec`, which hands ownership of the pixels to the image.
+    pub unsafe fn new_from_memory(
+        data: &[u8],
         width: i32,
         height: i32,
         bands: i32,
-        band_format: s::VipsBandFormat
+        band_format: BandFormat
     ) -> Result<VipsImage, VipsError>{
-        VipsImage::from_c_ptr(unsafe { s::vips_image_new_from_memory(
+        VipsImage::from_c_ptr(s::vips_image_new_from_memory(
             data.as_ptr() as *const c_void, data.len(),
-            width, height, bands, band_format
-        ) })
+            width, height, bands, band_format.to_raw()
+        ))
     }
 
     pub fn new_from_memory_copy(
@@ -102,17 +204,14 @@ impl VipsImage {
         width: i32,
         height: i32,
         bands: i32,
-        band_format: s::VipsBandFormat
+        band_format: BandFormat
     ) -> Result<VipsImage, VipsError>{
         VipsImage::from_c_ptr(unsafe { s::vips_image_new_from_memory_copy(
             data.as_ptr() as *const c_void, data.len(),
-            width, height, bands, band_format
+            width, height, bands, band_format.to_raw()
         ) })
     }
 
-    // TODO: vips_image_new_from_buffer()
-    // TODO: vips_image_new_from_source()
-
     pub fn new_matrix(width: i32, height: i32) -> Result<VipsImage, VipsError> {
         Ok(VipsImage::from_c_ptr(unsafe {
             s::vips_image_new_matrix(width, height)
@@ -170,13 +269,345 @@ impl VipsImage {
         Ok(())
     }
 
+    /// Encode the image in the format given by `suffix` (e.g. `".jpg"`,
+    /// `".webp"` or `".png"`) and return the encoded bytes.
+    ///
+    /// `options` is passed on to the saver, e.g. `"Q=90,strip"` for JPEG
+    /// files. Use `""` for no options.
+    pub fn write_to_buffer(&self, suffix: &str, options: &str) -> Result<Vec<u8>, VipsError> {
+        let suffix_c_str = if options.is_empty() {
+            CString::new(suffix)?
+        } else {
+            CString::new(format!("{suffix}[{options}]"))?
+        };
+
+        unsafe {
+            let mut buf: *mut c_void = std::ptr::null_mut();
+            let mut size: usize = 0;
+            if s::vips_image_write_to_buffer(
+                self.ptr,
+                suffix_c_str.as_ptr(),
+                &mut buf,
+                &mut size,
+                std::ptr::null::<c_char>()
+            ) != 0 {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            // the buffer was allocated with g_malloc, so copy it into memory
+            // owned by Rust and hand the original back to glib
+            let out = std::slice::from_raw_parts(buf as *const u8, size).to_vec();
+            s::g_free(buf);
+
+            Ok(out)
+        }
+    }
+
+    /// Render the whole image and return a copy of its pixels. Pixels are
+    /// stored band-interleaved, in row-major order and in the image's band
+    /// format.
+    pub fn write_to_memory(&self) -> Result<Vec<u8>, VipsError> {
+        unsafe {
+            let mut size: usize = 0;
+            let buf = s::vips_image_write_to_memory(self.ptr, &mut size);
+            if buf.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            // allocated with g_malloc, see `write_to_buffer`
+            let out = std::slice::from_raw_parts(buf as *const u8, size).to_vec();
+            s::g_free(buf);
+
+            Ok(out)
+        }
+    }
+
+    /// Render the image into a new image held in memory. If the image already
+    /// is a memory image, this is a cheap reference to the same pixels.
+    pub fn copy_memory(&self) -> Result<VipsImage, VipsError> {
+        let p = unsafe { s::vips_image_copy_memory(self.ptr) };
+        if p.is_null() {
+            return Err(VipsError::new_from_vips_state());
+        }
+        VipsImage::from_c_ptr(p)
+    }
+
+    /// Borrow the image's pixels without copying them. Pixels are laid out as
+    /// in `write_to_memory`.
+    ///
+    /// Images are usually evaluated lazily. If the pixels are not in memory
+    /// yet, the whole image is rendered into a new memory image with
+    /// `copy_memory`, which then replaces `self`. Later calls, and calls on
+    /// images made from memory, borrow the pixels directly. Other references
+    /// to the original image, e.g. clones or the copy held by the operation
+    /// cache, are left untouched. Use `as_slice` to get typed pixel values.
+    pub fn as_bytes(&mut self) -> Result<&[u8], VipsError> {
+        if !self.is_in_memory() {
+            *self = self.copy_memory()?;
+        }
+
+        unsafe {
+            let data = s::vips_image_get_data(self.ptr);
+            if data.is_null() {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            let size = self.width()
+                * self.height()
+                * self.nbands()
+                * s::vips_format_sizeof(s::vips_image_get_format(self.ptr)) as usize;
+
+            Ok(std::slice::from_raw_parts(data as *const u8, size))
+        }
+    }
+
+    /// Borrow the image's pixels as values of type `T` without copying them,
+    /// e.g. `as_slice::<f32>()` for an image with band format float. The
+    /// slice holds `width * height * nbands` values.
+    ///
+    /// Fails if `T` does not match the image's band format.
+    pub fn as_slice<T: Pixel>(&mut self) -> Result<&[T], VipsError> {
+        let format = self.format();
+        if format != T::BAND_FORMAT {
+            return Err(VipsError::new(format!(
+                "Cannot view image with band format {format} as `{}` (band format {})",
+                std::any::type_name::<T>(), T::BAND_FORMAT
+            )));
+        }
+
+        let bytes = self.as_bytes()?;
+        if bytes.as_ptr().align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(VipsError::new(format!(
+                "Image data is not aligned for `{}`", std::any::type_name::<T>()
+            )));
+        }
+
+        Ok(unsafe { std::slice::from_raw_parts(
+            bytes.as_ptr() as *const T,
+            bytes.len() / std::mem::size_of::<T>()
+        ) })
+    }
+
+    /// Whether the pixels are held in memory, so they can be borrowed without
+    /// rendering the image
+    fn is_in_memory(&self) -> bool {
+        let dtype = unsafe { (*self.ptr).dtype };
+        matches!(
+            dtype,
+            s::VipsImageType_VIPS_IMAGE_SETBUF
+                | s::VipsImageType_VIPS_IMAGE_SETBUF_FOREIGN
+                | s::VipsImageType_VIPS_IMAGE_MMAPIN
+                | s::VipsImageType_VIPS_IMAGE_MMAPINRW
+        )
+    }
+
     // --- Image Properties ---
 
+    pub fn width(&self) -> usize {
+        unsafe {
+            s::vips_image_get_width(self.ptr) as usize
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        unsafe {
+            s::vips_image_get_height(self.ptr) as usize
+        }
+    }
+
     pub fn nbands(&self) -> usize {
         unsafe {
             s::vips_image_get_bands(self.ptr) as usize
         }
     }
+
+    pub fn format(&self) -> BandFormat {
+        let raw = unsafe { s::vips_image_get_format(self.ptr) };
+        BandFormat::from_raw(raw).unwrap_or(BandFormat::NotSet)
+    }
+
+    pub fn coding(&self) -> Coding {
+        let raw = unsafe { s::vips_image_get_coding(self.ptr) };
+        Coding::from_raw(raw).unwrap_or(Coding::Error)
+    }
+
+    /// The interpretation set in the image header. This is not checked against
+    /// the image's format and bands, see `guess_interpretation`.
+    pub fn interpretation(&self) -> Interpretation {
+        let raw = unsafe { s::vips_image_get_interpretation(self.ptr) };
+        Interpretation::from_raw(raw).unwrap_or(Interpretation::Error)
+    }
+
+    /// A sane interpretation for the image, based on the header and the
+    /// image's format and bands
+    pub fn guess_interpretation(&self) -> Interpretation {
+        let raw = unsafe { s::vips_image_guess_interpretation(self.ptr) };
+        Interpretation::from_raw(raw).unwrap_or(Interpretation::Error)
+    }
+
+    /// Horizontal resolution in pixels per millimetre
+    pub fn xres(&self) -> f64 {
+        unsafe {
+            s::vips_image_get_xres(self.ptr)
+        }
+    }
+
+    /// Vertical resolution in pixels per millimetre
+    pub fn yres(&self) -> f64 {
+        unsafe {
+            s::vips_image_get_yres(self.ptr)
+        }
+    }
+
+    pub fn xoffset(&self) -> i32 {
+        unsafe {
+            s::vips_image_get_xoffset(self.ptr)
+        }
+    }
+
+    pub fn yoffset(&self) -> i32 {
+        unsafe {
+            s::vips_image_get_yoffset(self.ptr)
+        }
+    }
+
+    /// The filename Vips keeps for the image. This is the path for images
+    /// loaded from a file, but images made in memory may carry a placeholder
+    /// instead, e.g. `"vips_image_new_matrix"` for matrices.
+    pub fn filename(&self) -> Option<String> {
+        unsafe {
+            let p = s::vips_image_get_filename(self.ptr);
+            if p.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(p).to_string_lossy().into_owned())
+        }
+    }
+
+    /// The mode the image was opened in, e.g. `"p"` for a partial image or
+    /// `"t"` for a memory image
+    pub fn mode(&self) -> Option<String> {
+        unsafe {
+            let p = s::vips_image_get_mode(self.ptr);
+            if p.is_null() {
+                return None;
+            }
+            Some(CStr::from_ptr(p).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Number of pages in the file the image was loaded from. Loading all
+    /// pages stacks them vertically, each `page_height` pixels high.
+    pub fn n_pages(&self) -> i32 {
+        unsafe {
+            s::vips_image_get_n_pages(self.ptr)
+        }
+    }
+
+    /// Height of a single page of a multi-page image, or the image height if
+    /// the image has no pages
+    pub fn page_height(&self) -> i32 {
+        unsafe {
+            s::vips_image_get_page_height(self.ptr)
+        }
+    }
+
+    /// The EXIF orientation of the image, 1 - 8. Images without an orientation
+    /// tag are upright, i.e. 1.
+    pub fn orientation(&self) -> i32 {
+        unsafe {
+            s::vips_image_get_orientation(self.ptr)
+        }
+    }
+
+    /// Whether the last band of the image looks like an alpha channel, based
+    /// on the image's bands and interpretation
+    pub fn has_alpha(&self) -> bool {
+        unsafe {
+            s::vips_image_hasalpha(self.ptr) != 0
+        }
+    }
+
+    /// Multiply pixel values by `scale` and add `offset` to get the original
+    /// values, e.g. for images from scientific file formats
+    pub fn scale(&self) -> f64 {
+        unsafe {
+            s::vips_image_get_scale(self.ptr)
+        }
+    }
+
+    /// See `scale`
+    pub fn offset(&self) -> f64 {
+        unsafe {
+            s::vips_image_get_offset(self.ptr)
+        }
+    }
+}
+
+// === Helpers =================================================================
+
+/// Make sure a buffer of `len` values holds exactly one image of the given size
+fn check_pixel_count(len: usize, width: i32, height: i32, bands: i32) -> Result<(), VipsError> {
+    if width <= 0 || height <= 0 || bands <= 0 {
+        return Err(VipsError::new(format!(
+            "Invalid image size {width}x{height} with {bands} bands"
+        )));
+    }
+
+    let expected = width as usize * height as usize * bands as usize;
+    if len != expected {
+        return Err(VipsError::new(format!(
+            "Expected {expected} values for a {width}x{height} image with {bands} bands, got {len}"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Free the pixels handed to `new_from_vec` once the image is closed
+unsafe extern "C" fn free_vec_cb<T>(_image: *mut s::VipsImage, data: *mut c_void) {
+    drop(Box::from_raw(data as *mut Vec<T>));
+}
+
+// =============================================================================
+// === Pixel ===================================================================
+// =============================================================================
+
+/// A Rust type that holds a single band value of a pixel in one of Vips' band
+/// formats.
+///
+/// # Safety
+///
+/// Implementors must have exactly the size and memory layout of the band
+/// format given in `BAND_FORMAT`, and every bit pattern must be a valid value.
+pub unsafe trait Pixel: Copy {
+    const BAND_FORMAT: BandFormat;
+}
+
+unsafe impl Pixel for u8 { const BAND_FORMAT: BandFormat = BandFormat::UChar; }
+unsafe impl Pixel for i8 { const BAND_FORMAT: BandFormat = BandFormat::Char; }
+unsafe impl Pixel for u16 { const BAND_FORMAT: BandFormat = BandFormat::UShort; }
+unsafe impl Pixel for i16 { const BAND_FORMAT: BandFormat = BandFormat::Short; }
+unsafe impl Pixel for u32 { const BAND_FORMAT: BandFormat = BandFormat::UInt; }
+unsafe impl Pixel for i32 { const BAND_FORMAT: BandFormat = BandFormat::Int; }
+unsafe impl Pixel for f32 { const BAND_FORMAT: BandFormat = BandFormat::Float; }
+unsafe impl Pixel for f64 { const BAND_FORMAT: BandFormat = BandFormat::Double; }
+unsafe impl Pixel for Complex<f32> { const BAND_FORMAT: BandFormat = BandFormat::Complex; }
+unsafe impl Pixel for Complex<f64> { const BAND_FORMAT: BandFormat = BandFormat::DpComplex; }
+
+/// A complex band value, laid out like Vips' complex band formats: the real
+/// part followed by the imaginary part
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T
+}
+
+impl<T> Complex<T> {
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
 }
 
 // =============================================================================
@@ -216,4 +647,169 @@ mod tests {
         img.write_to_file(PathBuf::from("./data/test_out.jpg"))
             .expect("Could not save image to file");
     }
+
+    #[test]
+    fn image_from_buffer() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let data = std::fs::read("./data/test.jpg")
+            .expect("Could not read test file");
+
+        let img = VipsImage::new_from_buffer(&data, &LoadOptions::default())
+            .expect("Image could not be created from buffer");
+        assert_eq!(img.nbands(), 3);
+
+        // the image must not depend on the buffer it was loaded from
+        drop(data);
+        img.write_to_file(PathBuf::from("./data/test_from_buffer.jpg"))
+            .expect("Could not save image to file");
+
+        match VipsImage::new_from_buffer(b"definitely not an image", &LoadOptions::default()) {
+            Ok(_) => panic!("Loading an unknown format should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn image_to_buffer() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let png = img.write_to_buffer(".png", "")
+            .expect("Could not encode image as PNG");
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let jpeg = img.write_to_buffer(".jpg", "Q=50")
+            .expect("Could not encode image as JPEG");
+        assert_eq!(&jpeg[..2], b"\xff\xd8");
+
+        let reloaded = VipsImage::new_from_buffer(&jpeg, &LoadOptions::default())
+            .expect("Could not load encoded image");
+        assert_eq!(reloaded.nbands(), img.nbands());
+
+        match img.write_to_buffer(".not-a-format", "") {
+            Ok(_) => panic!("Saving to an unknown format should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn image_to_memory() {
+        ensure_vips_init_or_exit();
+
+        let mut img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let pixels = img.write_to_memory()
+            .expect("Could not write image to memory");
+        assert_eq!(pixels.len(), img.width() * img.height() * img.nbands());
+
+        // borrowing renders into a new image, the loaded one is not touched
+        let loaded = img.clone();
+        let bytes = img.as_bytes()
+            .expect("Could not borrow image data");
+        assert_eq!(bytes, &pixels[..]);
+        assert_ne!(img.ptr, loaded.ptr);
+
+        // the rendered image is borrowed from directly from then on
+        let rendered = img.ptr;
+        img.as_bytes().expect("Could not borrow image data");
+        assert_eq!(img.ptr, rendered);
+
+        let typed = img.as_slice::<u8>()
+            .expect("Could not borrow image data as u8");
+        assert_eq!(typed.len(), pixels.len());
+
+        match img.as_slice::<f32>() {
+            Ok(_) => panic!("Viewing uchar data as f32 should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn matrix_as_slice() {
+        ensure_vips_init_or_exit();
+
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let mut matrix = VipsImage::new_matrix_from_array(3, 2, &values)
+            .expect("Could not create matrix");
+
+        assert_eq!(matrix.as_slice::<f64>().expect("Could not borrow matrix data"), &values);
+        assert_eq!(
+            matrix.copy_memory().expect("Could not copy matrix")
+                .as_slice::<f64>().expect("Could not borrow matrix data"),
+            &values
+        );
+    }
+
+    #[test]
+    fn image_from_slice() {
+        ensure_vips_init_or_exit();
+
+        let values: Vec<u16> = (0..4 * 3 * 2).collect();
+        let mut img = VipsImage::new_from_slice(&values, 4, 3, 2)
+            .expect("Could not create image from slice");
+        assert_eq!((img.width(), img.height(), img.nbands()), (4, 3, 2));
+        assert_eq!(img.as_slice::<u16>().expect("Could not borrow image data"), &values[..]);
+
+        let complex = [Complex::new(1.0f32, -1.0), Complex::new(2.0, 0.5)];
+        let mut img = VipsImage::new_from_slice(&complex, 2, 1, 1)
+            .expect("Could not create complex image from slice");
+        assert_eq!(img.as_slice::<Complex<f32>>().expect("Could not borrow image data"), &complex);
+
+        match VipsImage::new_from_slice(&values, 4, 3, 3) {
+            Ok(_) => panic!("Creating an image from too few values should error"),
+            Err(_) => {}
+        }
+        match VipsImage::new_from_slice(&values, -4, 3, 2) {
+            Ok(_) => panic!("Creating an image with negative size should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn image_from_vec() {
+        ensure_vips_init_or_exit();
+
+        let values: Vec<f32> = (0..16).map(|v| v as f32 / 16.0).collect();
+        let expected = values.clone();
+        let img = VipsImage::new_from_vec(values, 4, 4, 1)
+            .expect("Could not create image from vec");
+
+        // pixels are not copied, but must stay valid while the image lives
+        let mut copy = img.clone();
+        drop(img);
+        assert_eq!(copy.as_slice::<f32>().expect("Could not borrow image data"), &expected[..]);
+    }
+
+    #[test]
+    fn image_properties() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+        assert_eq!(img.format(), BandFormat::UChar);
+        assert_eq!(img.coding(), Coding::None);
+        assert_eq!(img.interpretation(), Interpretation::Srgb);
+        assert_eq!(img.guess_interpretation(), Interpretation::Srgb);
+        assert_eq!(img.filename().as_deref(), Some("./data/test.jpg"));
+        assert_eq!(img.n_pages(), 1);
+        assert_eq!(img.page_height() as usize, img.height());
+        assert_eq!(img.orientation(), 1);
+        assert!(!img.has_alpha());
+        assert!(img.xres() > 0.0 && img.yres() > 0.0);
+        assert_eq!((img.xoffset(), img.yoffset()), (0, 0));
+        assert_eq!((img.scale(), img.offset()), (1.0, 0.0));
+
+        let matrix = VipsImage::new_matrix_from_array(2, 1, &[1.0, 2.0])
+            .expect("Could not create matrix");
+        assert_eq!(matrix.format(), BandFormat::Double);
+        assert_eq!(matrix.interpretation(), Interpretation::Matrix);
+        assert_eq!(matrix.filename().as_deref(), Some("vips_image_new_matrix"));
+        assert_eq!(matrix.mode().as_deref(), Some("t"));
+    }
 }
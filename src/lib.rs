@@ -12,10 +12,20 @@
 
 pub mod vips;
 pub mod image;
+pub mod connection;
+pub mod foreign;
+pub mod metadata;
+pub mod exif;
 pub mod error;
 pub mod operator;
+pub mod operation;
 pub mod arithmetic;
+pub mod statistics;
+pub mod conversion;
+pub mod convolution;
 pub mod colour;
+pub mod enums;
+pub mod generated;
 
 // =============================================================================
 // === EXPORTED SYMBOLS ========================================================
@@ -29,10 +39,22 @@ pub use crate::vips::{
     VipsHandle
 };
 
-pub use crate::error::VipsError;
-pub use crate::image::VipsImage;
+pub use crate::error::{VipsError, VipsErrorLine, ErrorKind};
+pub use crate::enums::*;
+pub use crate::image::{VipsImage, Pixel, Complex};
+pub use crate::connection::{VipsSource, VipsTarget};
+pub use crate::foreign::*;
+pub use crate::metadata::MetaValue;
+pub use crate::exif::{Exif, GpsPosition, Autorotation};
 
 pub use crate::operator::*;
+pub use crate::operation::{Operation, ArgumentSpec, ArgumentDirection, Outputs};
 
 pub use crate::arithmetic::*;
+pub use crate::statistics::{Extremum, BandStats, StatsMatrix};
 pub use crate::colour::*;
+pub use crate::conversion::*;
+pub use crate::convolution::*;
+// empty until generated against an installed libvips
+#[allow(unused_imports)]
+pub use crate::generated::*;
@@ -0,0 +1,232 @@
+//! Reading and writing image metadata, e.g. EXIF, XMP or ICC profiles
+
+use std::ffi::{CStr, CString, c_void};
+
+use vips_sys as s;
+
+use crate::*;
+use crate::operator::{set_argument, get_property};
+
+// =============================================================================
+// === MetaValue ===============================================================
+// =============================================================================
+
+/// The value of a metadata field of an image
+#[derive(Clone)]
+pub enum MetaValue {
+    Int(i32),
+    Double(f64),
+    String(String),
+    /// Binary data, e.g. `"exif-data"` or `"icc-profile-data"`
+    Blob(Vec<u8>),
+    /// A reference-counted string, which Vips uses for the parsed EXIF fields
+    RefString(String),
+    ArrayInt(Vec<i32>),
+    ArrayDouble(Vec<f64>),
+    Image(VipsImage)
+}
+
+// =============================================================================
+// === VipsImage ===============================================================
+// =============================================================================
+
+impl VipsImage {
+    /// Get a copy of the metadata field `name`. Header fields like `"width"`
+    /// can be read as well.
+    pub fn get(&self, name: &str) -> Result<MetaValue, VipsError> {
+        let name_c_str = CString::new(name)?;
+
+        unsafe {
+            let mut raw: s::GValue = std::mem::zeroed();
+            if s::vips_image_get(self.ptr, name_c_str.as_ptr(), &mut raw) != 0 {
+                return Err(VipsError::new_from_vips_state());
+            }
+
+            // vips_image_get initialised raw with a copy of the field
+            GValue::from_raw(raw).get()
+        }
+    }
+
+    /// Whether the image has the field `name`. Check this before `get` for
+    /// fields that are often missing, since a failed `get` leaves a message
+    /// in the error buffer shared by all threads.
+    pub(crate) fn has_field(&self, name: &str) -> bool {
+        let name_c_str = match CString::new(name) {
+            Ok(name_c_str) => name_c_str,
+            Err(_) => return false
+        };
+
+        unsafe { s::vips_image_get_typeof(self.ptr, name_c_str.as_ptr()) != 0 }
+    }
+
+    /// Set the metadata field `name` to `value`, replacing any previous value.
+    ///
+    /// Images are shared between clones of a `VipsImage` and with the
+    /// operation cache, so `self` is first replaced by a copy with its own
+    /// metadata. The copy shares the pixels and is cheap to make, but each
+    /// call adds one more step that pixels pass through when the image is
+    /// rendered.
+    pub fn set(&mut self, name: &str, value: MetaValue) -> Result<(), VipsError> {
+        let name_c_str = CString::new(name)?;
+
+        let mut g_value = match value.to_gvalue() {
+            Some(g_value) => g_value,
+            None => return Err(VipsError::new(
+                format!("Could not convert value for metadata field `{name}`")
+            ))
+        };
+
+        *self = unsafe { copy_uncached(self) }?;
+        unsafe {
+            // vips_image_set copies the value
+            s::vips_image_set(self.ptr, name_c_str.as_ptr(), g_value.as_mut_ptr());
+        }
+
+        Ok(())
+    }
+
+    /// Remove the metadata field `name`. Returns `false` if the image has no
+    /// such field.
+    ///
+    /// As with `set`, the image is first replaced by a copy, and `false` is
+    /// returned if that fails.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let name_c_str = match CString::new(name) {
+            Ok(name_c_str) => name_c_str,
+            Err(_) => return false
+        };
+        match unsafe { copy_uncached(self) } {
+            Ok(copy) => *self = copy,
+            Err(_) => return false
+        }
+
+        unsafe {
+            s::vips_image_remove(self.ptr, name_c_str.as_ptr()) != 0
+        }
+    }
+
+    /// The names of all header and metadata fields of the image
+    pub fn fields(&self) -> Vec<String> {
+        let mut fields = Vec::new();
+
+        unsafe {
+            let names = s::vips_image_get_fields(self.ptr);
+            if names.is_null() {
+                return fields;
+            }
+
+            let mut name = names;
+            while !(*name).is_null() {
+                fields.push(CStr::from_ptr(*name).to_string_lossy().into_owned());
+                name = name.add(1);
+            }
+
+            s::g_strfreev(names);
+        }
+
+        fields
+    }
+}
+
+// === Helpers =================================================================
+
+/// Copy `image` with the `copy` operation. The operation cache is bypassed, as
+/// it would return the same copy to everyone copying the image.
+unsafe fn copy_uncached(image: &VipsImage) -> Result<VipsImage, VipsError> {
+    let op_name_c_str = CString::new("copy")?;
+    let op = s::vips_operation_new(op_name_c_str.as_ptr());
+    if op.is_null() {
+        return Err(VipsError::new_from_vips_state().with_operation("copy"));
+    }
+
+    let out = set_argument(op, "in", image).and_then(|_| {
+        if s::vips_object_build(op as *mut s::VipsObject) != 0 {
+            return Err(VipsError::new_from_vips_state());
+        }
+        get_property(op as *mut s::GObject, "out")
+    });
+
+    s::vips_object_unref_outputs(op as *mut s::VipsObject);
+    s::g_object_unref(op as *mut c_void);
+
+    out.map_err(|e| e.with_operation("copy"))
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn metadata_get() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
+            .expect("Image could not be created from file");
+
+        let fields = img.fields();
+        assert!(fields.iter().any(|field| field == "width"));
+        assert!(fields.iter().any(|field| field == "exif-data"));
+
+        match img.get("width").expect("Could not get width") {
+            MetaValue::Int(width) => assert_eq!(width as usize, img.width()),
+            _ => panic!("Width should be an int")
+        }
+        match img.get("exif-data").expect("Could not get EXIF data") {
+            MetaValue::Blob(data) => assert!(!data.is_empty()),
+            _ => panic!("EXIF data should be a blob")
+        }
+        match img.get("exif-ifd0-Orientation").expect("Could not get EXIF orientation") {
+            MetaValue::RefString(orientation) => assert!(orientation.starts_with('1')),
+            _ => panic!("EXIF fields should be ref-strings")
+        }
+
+        match img.get("no-such-field") {
+            Ok(_) => panic!("Getting a missing field should error"),
+            Err(_) => {}
+        }
+    }
+
+    #[test]
+    fn metadata_set_remove() {
+        ensure_vips_init_or_exit();
+
+        let mut img = VipsImage::new_matrix_from_array(2, 1, &[1.0, 2.0])
+            .expect("Could not create matrix");
+
+        img.set("test-int", MetaValue::Int(42)).expect("Could not set int");
+        img.set("test-string", MetaValue::String("hello".to_string())).expect("Could not set string");
+        img.set("test-blob", MetaValue::Blob(vec![1, 2, 3])).expect("Could not set blob");
+        img.set("test-ints", MetaValue::ArrayInt(vec![4, 5])).expect("Could not set int array");
+        img.set("test-doubles", MetaValue::ArrayDouble(vec![0.5])).expect("Could not set double array");
+        let other = VipsImage::new_matrix(1, 1).expect("Could not create matrix");
+        img.set("test-image", MetaValue::Image(other.clone())).expect("Could not set image");
+
+        assert!(matches!(img.get("test-int"), Ok(MetaValue::Int(42))));
+        assert!(matches!(img.get("test-string"), Ok(MetaValue::String(v)) if v == "hello"));
+        assert!(matches!(img.get("test-blob"), Ok(MetaValue::Blob(v)) if v == [1, 2, 3]));
+        assert!(matches!(img.get("test-ints"), Ok(MetaValue::ArrayInt(v)) if v == [4, 5]));
+        assert!(matches!(img.get("test-doubles"), Ok(MetaValue::ArrayDouble(v)) if v == [0.5]));
+        assert!(matches!(img.get("test-image"), Ok(MetaValue::Image(v)) if v.ptr == other.ptr));
+
+        img.set("test-int", MetaValue::Double(1.5)).expect("Could not replace int");
+        assert!(matches!(img.get("test-int"), Ok(MetaValue::Double(v)) if v == 1.5));
+
+        assert!(img.remove("test-int"));
+        assert!(!img.remove("test-int"));
+        assert!(!img.fields().iter().any(|field| field == "test-int"));
+
+        // changes do not show through other references to the image
+        let shared = img.clone();
+        img.set("test-shared", MetaValue::Int(1)).expect("Could not set int");
+        assert!(img.fields().iter().any(|field| field == "test-shared"));
+        assert!(!shared.fields().iter().any(|field| field == "test-shared"));
+        assert!(img.remove("test-string"));
+        assert!(shared.fields().iter().any(|field| field == "test-string"));
+    }
+}
@@ -0,0 +1,323 @@
+//! Calling operations by name, for when they are only known at runtime
+//!
+//! ```ignore
+//! let out = Operation::new("linear")?
+//!     .set("in", &img)?
+//!     .set("a", [2.].as_slice())?
+//!     .set("b", [1.].as_slice())?
+//!     .call()?;
+//! ```
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char, c_int, c_void};
+
+use vips_sys as s;
+
+use crate::{VipsError, ErrorKind, MetaValue};
+use crate::operator::{GValue, ToGValue, set_argument, meta_value_from_gvalue};
+
+/// The outputs of an operation, by argument name. Enums and booleans are
+/// returned as `MetaValue::Int`.
+pub type Outputs = HashMap<String, MetaValue>;
+
+// =============================================================================
+// === ArgumentSpec ============================================================
+// =============================================================================
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ArgumentDirection {
+    Input,
+    Output
+}
+
+/// Description of an argument of an operation, see `Operation::introspect`
+#[derive(Clone)]
+pub struct ArgumentSpec {
+    pub name: String,
+    /// Name of the `GType` of the argument, e.g. `"VipsImage"`, `"gdouble"`
+    /// or `"VipsInterpretation"`
+    pub value_type: String,
+    pub direction: ArgumentDirection,
+    /// Whether the operation fails if the argument is not set
+    pub required: bool,
+    /// The value used for unset optional arguments. Only given for numbers,
+    /// booleans, strings and enums.
+    pub default: Option<MetaValue>,
+    /// Smallest allowed value of numeric arguments
+    pub min: Option<f64>,
+    /// Largest allowed value of numeric arguments
+    pub max: Option<f64>,
+    /// Short description of the argument
+    pub blurb: String
+}
+
+// =============================================================================
+// === Operation ===============================================================
+// =============================================================================
+
+/// An operation whose arguments are set one by one before it is called
+pub struct Operation {
+    name: String,
+    op: *mut s::VipsOperation
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if !self.op.is_null() {
+            unsafe { s::g_object_unref(self.op as *mut c_void) };
+        }
+    }
+}
+
+impl Operation {
+    /// Create the operation with the nickname `name`, e.g. `"invert"`
+    pub fn new(name: &str) -> Result<Operation, VipsError> {
+        let op_name_c_str = CString::new(name)?;
+        let op = unsafe { s::vips_operation_new(op_name_c_str.as_ptr()) };
+        if op.is_null() {
+            return Err(VipsError::new_from_vips_state()
+                .with_default_kind(ErrorKind::InvalidArgument)
+                .with_operation(name));
+        }
+
+        Ok(Operation { name: name.to_string(), op })
+    }
+
+    /// Nickname of the operation
+    pub fn name(&self) -> &str { self.name.as_str() }
+
+    /// Set the input argument `name`. Integers and nickname strings are
+    /// accepted for enum and flags arguments. Setting `None` leaves the
+    /// argument unset.
+    pub fn set<T: ToGValue>(self, name: &str, value: T) -> Result<Operation, VipsError> {
+        unsafe { set_argument(self.op, name, &value) }
+            .map_err(|e| e.with_operation(self.name.as_str()))?;
+        Ok(self)
+    }
+
+    /// Run the operation and read all outputs it set. Outputs of types
+    /// `MetaValue` can not hold are left out.
+    pub fn call(self) -> Result<Outputs, VipsError> {
+        unsafe {
+            let built_op = s::vips_cache_operation_build(self.op);
+            if built_op.is_null() {
+                // a failed build may have set some outputs already
+                s::vips_object_unref_outputs(self.op as *mut s::VipsObject);
+                return Err(VipsError::new_from_vips_state().with_operation(self.name.as_str()));
+            }
+
+            let outputs = read_outputs(built_op)
+                .map_err(|e| e.with_operation(self.name.as_str()));
+
+            s::vips_object_unref_outputs(built_op as *mut s::VipsObject);
+            s::g_object_unref(built_op as *mut c_void);
+
+            outputs
+        }
+    }
+
+    /// The arguments of the operation with the nickname `name`. Deprecated
+    /// arguments are left out.
+    pub fn introspect(name: &str) -> Result<Vec<ArgumentSpec>, VipsError> {
+        let op = Operation::new(name)?;
+
+        unsafe {
+            let specs = arguments(op.op)?.into_iter()
+                .map(|(name, pspec, flags)| argument_spec(name, pspec, flags))
+                .collect();
+
+            Ok(specs)
+        }
+    }
+}
+
+// === Helpers =================================================================
+
+fn has_flag(flags: c_int, flag: s::VipsArgumentFlags) -> bool {
+    flags as u32 & flag != 0
+}
+
+/// Name, `GParamSpec` and `VipsArgumentFlags` of the inputs and outputs of
+/// `op`
+unsafe fn arguments(
+    op: *mut s::VipsOperation
+) -> Result<Vec<(String, *mut s::GParamSpec, c_int)>, VipsError> {
+    let mut names: *mut *const c_char = std::ptr::null_mut();
+    let mut flags: *mut c_int = std::ptr::null_mut();
+    let mut n_args: c_int = 0;
+    if s::vips_object_get_args(op as *mut s::VipsObject, &mut names, &mut flags, &mut n_args) != 0 {
+        return Err(VipsError::new_from_vips_state());
+    }
+
+    let mut out = Vec::new();
+    for i in 0..n_args.max(0) as usize {
+        let arg_flags = *flags.add(i);
+        if has_flag(arg_flags, s::VipsArgumentFlags_VIPS_ARGUMENT_DEPRECATED)
+            || !(has_flag(arg_flags, s::VipsArgumentFlags_VIPS_ARGUMENT_INPUT)
+                || has_flag(arg_flags, s::VipsArgumentFlags_VIPS_ARGUMENT_OUTPUT))
+        {
+            continue;
+        }
+
+        let mut pspec: *mut s::GParamSpec = std::ptr::null_mut();
+        let mut argument_class: *mut s::VipsArgumentClass = std::ptr::null_mut();
+        let mut argument_instance: *mut s::VipsArgumentInstance = std::ptr::null_mut();
+        if s::vips_object_get_argument(
+            op as *mut s::VipsObject,
+            *names.add(i),
+            &mut pspec,
+            &mut argument_class,
+            &mut argument_instance
+        ) != 0 {
+            return Err(VipsError::new_from_vips_state());
+        }
+
+        let name = CStr::from_ptr(*names.add(i)).to_string_lossy().into_owned();
+        out.push((name, pspec, arg_flags));
+    }
+
+    Ok(out)
+}
+
+unsafe fn argument_spec(name: String, pspec: *mut s::GParamSpec, flags: c_int) -> ArgumentSpec {
+    let value_type = (*pspec).value_type;
+    let fundamental_type = s::g_type_fundamental(value_type);
+
+    let (min, max) = if value_type == s::define_G_TYPE_INT {
+        let pspec = pspec as *mut s::GParamSpecInt;
+        (Some((*pspec).minimum as f64), Some((*pspec).maximum as f64))
+    } else if value_type == s::define_G_TYPE_UINT64 {
+        let pspec = pspec as *mut s::GParamSpecUInt64;
+        (Some((*pspec).minimum as f64), Some((*pspec).maximum as f64))
+    } else if value_type == s::define_G_TYPE_DOUBLE {
+        let pspec = pspec as *mut s::GParamSpecDouble;
+        (Some((*pspec).minimum), Some((*pspec).maximum))
+    } else {
+        (None, None)
+    };
+
+    // objects and boxed values like arrays have no useful default
+    let default = if fundamental_type == s::define_G_TYPE_OBJECT
+        || fundamental_type == s::define_G_TYPE_BOXED
+    {
+        None
+    } else {
+        let default = s::g_param_spec_get_default_value(pspec);
+        if default.is_null() {
+            None
+        } else {
+            meta_value_from_gvalue(&*default).ok()
+        }
+    };
+
+    let blurb = s::g_param_spec_get_blurb(pspec);
+
+    ArgumentSpec {
+        name,
+        value_type: CStr::from_ptr(s::g_type_name(value_type)).to_string_lossy().into_owned(),
+        direction: if has_flag(flags, s::VipsArgumentFlags_VIPS_ARGUMENT_OUTPUT) {
+            ArgumentDirection::Output
+        } else {
+            ArgumentDirection::Input
+        },
+        required: has_flag(flags, s::VipsArgumentFlags_VIPS_ARGUMENT_REQUIRED),
+        default,
+        min,
+        max,
+        blurb: if blurb.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(blurb).to_string_lossy().into_owned()
+        }
+    }
+}
+
+/// Read the outputs of the built operation `op` that were set, skipping those
+/// whose type has no `MetaValue`
+unsafe fn read_outputs(op: *mut s::VipsOperation) -> Result<Outputs, VipsError> {
+    let mut outputs = Outputs::new();
+
+    for (name, pspec, flags) in arguments(op)? {
+        let name_c_str = CString::new(name.as_str())?;
+        if !has_flag(flags, s::VipsArgumentFlags_VIPS_ARGUMENT_OUTPUT)
+            || s::vips_object_argument_isset(op as *mut s::VipsObject, name_c_str.as_ptr()) == 0
+        {
+            continue;
+        }
+
+        let mut g_value = GValue::new((*pspec).value_type);
+        s::g_object_get_property(op as *mut s::GObject, name_c_str.as_ptr(), g_value.as_mut_ptr());
+        if let Ok(value) = g_value.get() {
+            outputs.insert(name, value);
+        }
+    }
+
+    Ok(outputs)
+}
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn introspect() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let specs = Operation::introspect("embed").expect("Could not introspect operation");
+
+        let input = specs.iter().find(|a| a.name == "in").expect("No input argument");
+        assert_eq!(input.direction, ArgumentDirection::Input);
+        assert_eq!(input.value_type, "VipsImage");
+        assert!(input.required);
+
+        let out = specs.iter().find(|a| a.name == "out").expect("No output argument");
+        assert_eq!(out.direction, ArgumentDirection::Output);
+
+        let extend = specs.iter().find(|a| a.name == "extend").expect("No extend argument");
+        assert!(!extend.required);
+        assert!(matches!(extend.default, Some(MetaValue::Int(_))));
+
+        let width = specs.iter().find(|a| a.name == "width").expect("No width argument");
+        assert!(width.min.is_some() && width.max.is_some());
+
+        match Operation::introspect("no-such-operation") {
+            Ok(_) => panic!("Introspecting an unknown operation should error"),
+            Err(e) => assert_eq!(e.operation(), Some("no-such-operation"))
+        }
+    }
+
+    #[test]
+    fn call() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let img = VipsImage::new_matrix_from_array(2, 1, &[1., 2.])
+            .expect("Could not create matrix");
+
+        let outputs = Operation::new("flip").expect("Could not create operation")
+            .set("in", &img).expect("Could not set input")
+            .set("direction", "horizontal").expect("Could not set direction")
+            .call().expect("Could not call operation");
+        let mut flipped = match outputs.get("out") {
+            Some(MetaValue::Image(image)) => image.clone(),
+            _ => panic!("Output is not an image")
+        };
+        assert_eq!(flipped.as_slice::<f64>().expect("Could not read pixels"), &[2., 1.]);
+
+        let outputs = Operation::new("avg").expect("Could not create operation")
+            .set("in", &img).expect("Could not set input")
+            .call().expect("Could not call operation");
+        assert!(matches!(outputs.get("out"), Some(MetaValue::Double(v)) if *v == 1.5));
+
+        match Operation::new("avg").expect("Could not create operation").set("nope", 1) {
+            Ok(_) => panic!("Setting an unknown argument should error"),
+            Err(e) => assert_eq!(e.kind(), ErrorKind::InvalidArgument)
+        }
+    }
+}
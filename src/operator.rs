@@ -1,15 +1,61 @@
-use std::ffi::c_void;
+use std::ffi::{CStr, CString, c_int, c_void};
 
 use vips_sys as s;
 
-use crate::VipsImage;
+use crate::{VipsImage, VipsSource, VipsTarget, VipsError, ErrorKind, MetaValue};
+
+/// An initialised `GValue` that owns its contents. Dropping it unsets the
+/// value, which releases the refs it holds on images, arrays and blobs.
+pub struct GValue {
+    value: s::GValue
+}
+
+impl Drop for GValue {
+    fn drop(&mut self) {
+        unsafe { s::g_value_unset(&mut self.value) };
+    }
+}
+
+impl GValue {
+    /// Create a value of type `g_type` holding the type's default
+    pub fn new(g_type: s::GType) -> GValue {
+        unsafe {
+            let mut value: s::GValue = std::mem::zeroed();
+            s::g_value_init(&mut value, g_type);
+            GValue { value }
+        }
+    }
+
+    /// Take ownership of `value`
+    ///
+    /// # Safety
+    ///
+    /// `value` must be initialised and must not be unset by anything else.
+    pub unsafe fn from_raw(value: s::GValue) -> GValue {
+        GValue { value }
+    }
+
+    /// The `GType` of the held value
+    pub fn value_type(&self) -> s::GType { self.value.g_type }
+
+    /// Convert the held value to `T`, copying its contents
+    pub fn get<T: FromGValue>(&self) -> Result<T, VipsError> {
+        unsafe { T::from_gvalue(&self.value) }
+    }
+
+    pub fn as_raw(&self) -> &s::GValue { &self.value }
+
+    pub fn as_ptr(&self) -> *const s::GValue { &self.value }
+
+    pub fn as_mut_ptr(&mut self) -> *mut s::GValue { &mut self.value }
+}
 
 pub trait ToGValue {
-    fn to_gvalue(&self) -> Option<s::GValue>;
+    fn to_gvalue(&self) -> Option<GValue>;
 }
 
 impl<T> ToGValue for Option<T> where T: ToGValue {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         match self {
             Some(v) => v.to_gvalue(),
             None => None
@@ -18,17 +64,12 @@ impl<T> ToGValue for Option<T> where T: ToGValue {
 }
 
 impl ToGValue for i32 {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::define_G_TYPE_INT
-            );
+            let mut g_value = GValue::new(s::define_G_TYPE_INT);
     
             s::g_value_set_int(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 *self
             );
     
@@ -38,17 +79,12 @@ impl ToGValue for i32 {
 }
 
 impl ToGValue for u32 {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::define_G_TYPE_UINT
-            );
+            let mut g_value = GValue::new(s::define_G_TYPE_UINT);
     
             s::g_value_set_uint(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 *self
             );
     
@@ -57,18 +93,28 @@ impl ToGValue for u32 {
     }
 }
 
-impl ToGValue for f32 {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+impl ToGValue for u64 {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::define_G_TYPE_FLOAT
+            let mut g_value = GValue::new(s::define_G_TYPE_UINT64);
+
+            s::g_value_set_uint64(
+                g_value.as_mut_ptr(),
+                *self
             );
+
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for f32 {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::define_G_TYPE_FLOAT);
     
             s::g_value_set_float(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 *self
             );
     
@@ -78,17 +124,12 @@ impl ToGValue for f32 {
 }
 
 impl ToGValue for f64 {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::define_G_TYPE_DOUBLE
-            );
+            let mut g_value = GValue::new(s::define_G_TYPE_DOUBLE);
     
             s::g_value_set_double(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 *self
             );
     
@@ -98,17 +139,12 @@ impl ToGValue for f64 {
 }
 
 impl ToGValue for bool {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::define_G_TYPE_BOOLEAN
-            );
+            let mut g_value = GValue::new(s::define_G_TYPE_BOOLEAN);
     
             s::g_value_set_boolean(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 *self as i32
             );
     
@@ -119,17 +155,12 @@ impl ToGValue for bool {
 
 // TODO
 impl<'a> ToGValue for &[f64] {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
-    
-            s::g_value_init(
-                &mut g_value,
-                s::vips_array_double_get_type()
-            );
+            let mut g_value = GValue::new(s::vips_array_double_get_type());
 
             s::vips_value_set_array_double(
-                &mut g_value, self.as_ptr(),
+                g_value.as_mut_ptr(), self.as_ptr(),
                 self.len().try_into().ok()?);
 
             return Some(g_value);
@@ -137,26 +168,561 @@ impl<'a> ToGValue for &[f64] {
     }
 }
 
+impl ToGValue for &[i32] {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::vips_array_int_get_type());
+
+            s::vips_value_set_array_int(
+                g_value.as_mut_ptr(), self.as_ptr(),
+                self.len().try_into().ok()?);
+
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for &[&VipsImage] {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::vips_array_image_get_type());
+
+            s::vips_value_set_array_image(
+                g_value.as_mut_ptr(),
+                self.len().try_into().ok()?
+            );
+
+            // the array holds a ref to each image, which is dropped when the
+            // value is unset
+            let array = s::vips_value_get_array_image(g_value.as_ptr(), std::ptr::null_mut());
+            for (i, image) in self.iter().enumerate() {
+                s::g_object_ref(image.ptr as *mut c_void);
+                *array.add(i) = image.ptr;
+            }
+
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for &str {
+    fn to_gvalue(&self) -> Option<GValue> {
+        let c_str = CString::new(*self).ok()?;
+
+        unsafe {
+            let mut g_value = GValue::new(s::define_G_TYPE_STRING);
+
+            // g_value_set_string copies the string
+            s::g_value_set_string(
+                g_value.as_mut_ptr(),
+                c_str.as_ptr()
+            );
+
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for String {
+    fn to_gvalue(&self) -> Option<GValue> {
+        self.as_str().to_gvalue()
+    }
+}
+
+/// Byte slices are copied into a `VipsBlob` owned by the `GValue`, so the
+/// slice does not need to outlive the operation it is passed to.
+impl ToGValue for &[u8] {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::vips_blob_get_type());
+
+            let blob = s::vips_blob_copy(
+                self.as_ptr() as *const c_void,
+                self.len()
+            );
+            if blob.is_null() {
+                return None;
+            }
+
+            // the GValue takes its own ref to the blob
+            s::g_value_set_boxed(g_value.as_mut_ptr(), blob as *const c_void);
+            s::vips_area_unref(blob as *mut s::VipsArea);
+
+            return Some(g_value);
+        }
+    }
+}
+
 impl ToGValue for VipsImage {
-    fn to_gvalue(&self) -> Option<s::GValue> {
+    fn to_gvalue(&self) -> Option<GValue> {
         unsafe {
-            let mut g_value: s::GValue = std::mem::zeroed();
+            let mut g_value = GValue::new(s::vips_image_get_type());
     
-            s::g_value_init(
-                &mut g_value,
-                s::vips_image_get_type()
+            s::g_value_set_object(
+                g_value.as_mut_ptr(),
+                self.ptr as *mut c_void
             );
     
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for VipsSource {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::vips_source_get_type());
+
             s::g_value_set_object(
-                &mut g_value,
+                g_value.as_mut_ptr(),
                 self.ptr as *mut c_void
             );
-    
+
+            return Some(g_value);
+        }
+    }
+}
+
+impl ToGValue for VipsTarget {
+    fn to_gvalue(&self) -> Option<GValue> {
+        unsafe {
+            let mut g_value = GValue::new(s::vips_target_get_type());
+
+            s::g_value_set_object(
+                g_value.as_mut_ptr(),
+                self.ptr as *mut c_void
+            );
+
             return Some(g_value);
         }
     }
 }
 
+// references to objects, for argument structs that borrow them
+
+impl ToGValue for &VipsImage {
+    fn to_gvalue(&self) -> Option<GValue> {
+        (*self).to_gvalue()
+    }
+}
+
+impl ToGValue for &VipsSource {
+    fn to_gvalue(&self) -> Option<GValue> {
+        (*self).to_gvalue()
+    }
+}
+
+impl ToGValue for &VipsTarget {
+    fn to_gvalue(&self) -> Option<GValue> {
+        (*self).to_gvalue()
+    }
+}
+
+impl ToGValue for MetaValue {
+    fn to_gvalue(&self) -> Option<GValue> {
+        match self {
+            MetaValue::Int(v) => v.to_gvalue(),
+            MetaValue::Double(v) => v.to_gvalue(),
+            MetaValue::String(v) => v.as_str().to_gvalue(),
+            MetaValue::RefString(v) => {
+                let c_str = CString::new(v.as_str()).ok()?;
+
+                unsafe {
+                    let mut g_value = GValue::new(s::vips_ref_string_get_type());
+
+                    // the string is copied into a new ref-string
+                    s::vips_value_set_ref_string(
+                        g_value.as_mut_ptr(),
+                        c_str.as_ptr()
+                    );
+
+                    return Some(g_value);
+                }
+            },
+            MetaValue::Blob(v) => v.as_slice().to_gvalue(),
+            MetaValue::ArrayInt(v) => v.as_slice().to_gvalue(),
+            MetaValue::ArrayDouble(v) => v.as_slice().to_gvalue(),
+            MetaValue::Image(v) => v.to_gvalue()
+        }
+    }
+}
+
+/// Conversion from a `GValue`, e.g. for reading the outputs of an operation
+pub trait FromGValue: Sized {
+    /// Convert the value held by `value`, copying its contents
+    ///
+    /// # Safety
+    ///
+    /// `value` must be an initialised `GValue`.
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError>;
+}
+
+impl FromGValue for MetaValue {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        meta_value_from_gvalue(value)
+    }
+}
+
+impl FromGValue for i32 {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::Int(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not an integer"))
+        }
+    }
+}
+
+impl FromGValue for bool {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::Int(v) => Ok(v != 0),
+            _ => Err(VipsError::new("Value is not a boolean"))
+        }
+    }
+}
+
+impl FromGValue for f64 {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::Double(v) => Ok(v),
+            MetaValue::Int(v) => Ok(v as f64),
+            _ => Err(VipsError::new("Value is not a number"))
+        }
+    }
+}
+
+impl FromGValue for String {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::String(v) | MetaValue::RefString(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not a string"))
+        }
+    }
+}
+
+impl FromGValue for Vec<i32> {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::ArrayInt(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not an integer array"))
+        }
+    }
+}
+
+impl FromGValue for Vec<f64> {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::ArrayDouble(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not a double array"))
+        }
+    }
+}
+
+impl FromGValue for Vec<u8> {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::Blob(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not a blob"))
+        }
+    }
+}
+
+impl FromGValue for VipsImage {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        match meta_value_from_gvalue(value)? {
+            MetaValue::Image(v) => Ok(v),
+            _ => Err(VipsError::new("Value is not an image"))
+        }
+    }
+}
+
+/// Unsigned integers and the raw value of flags
+impl FromGValue for u32 {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        if value.g_type == s::define_G_TYPE_UINT {
+            Ok(s::g_value_get_uint(value))
+        } else if s::g_type_fundamental(value.g_type) == s::define_G_TYPE_FLAGS {
+            Ok(s::g_value_get_flags(value))
+        } else {
+            Err(VipsError::new("Value is not an unsigned integer or flags"))
+        }
+    }
+}
+
+impl FromGValue for u64 {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        if value.g_type == s::define_G_TYPE_UINT64 {
+            Ok(s::g_value_get_uint64(value))
+        } else {
+            Err(VipsError::new("Value is not a 64-bit unsigned integer"))
+        }
+    }
+}
+
+impl FromGValue for Vec<VipsImage> {
+    unsafe fn from_gvalue(value: &s::GValue) -> Result<Self, VipsError> {
+        if value.g_type != s::vips_array_image_get_type() {
+            return Err(VipsError::new("Value is not an image array"));
+        }
+
+        let mut n: c_int = 0;
+        let array = s::vips_value_get_array_image(value, &mut n);
+        if array.is_null() || n <= 0 {
+            return Ok(Vec::new());
+        }
+
+        // the array keeps its refs, each returned image holds a new one
+        std::slice::from_raw_parts(array, n as usize).iter()
+            .map(|&image| {
+                if !image.is_null() {
+                    s::g_object_ref(image as *mut c_void);
+                }
+                VipsImage::from_c_ptr(image)
+            })
+            .collect()
+    }
+}
+
+/// Convert `value` to a `MetaValue`, copying its contents. Enums and booleans
+/// are returned as `MetaValue::Int`.
+pub(crate) unsafe fn meta_value_from_gvalue(value: &s::GValue) -> Result<MetaValue, VipsError> {
+    let value_type = value.g_type;
+    let fundamental_type = s::g_type_fundamental(value_type);
+
+    if value_type == s::define_G_TYPE_INT {
+        Ok(MetaValue::Int(s::g_value_get_int(value)))
+    } else if fundamental_type == s::define_G_TYPE_ENUM {
+        Ok(MetaValue::Int(s::g_value_get_enum(value)))
+    } else if value_type == s::define_G_TYPE_BOOLEAN {
+        Ok(MetaValue::Int(s::g_value_get_boolean(value)))
+    } else if value_type == s::define_G_TYPE_DOUBLE {
+        Ok(MetaValue::Double(s::g_value_get_double(value)))
+    } else if value_type == s::define_G_TYPE_STRING {
+        let p = s::g_value_get_string(value);
+        if p.is_null() {
+            return Ok(MetaValue::String(String::new()));
+        }
+        Ok(MetaValue::String(CStr::from_ptr(p).to_string_lossy().into_owned()))
+    } else if value_type == s::vips_ref_string_get_type() {
+        let p = s::vips_value_get_ref_string(value, std::ptr::null_mut());
+        if p.is_null() {
+            return Ok(MetaValue::RefString(String::new()));
+        }
+        Ok(MetaValue::RefString(CStr::from_ptr(p).to_string_lossy().into_owned()))
+    } else if value_type == s::vips_blob_get_type() {
+        let mut length: usize = 0;
+        let p = s::vips_value_get_blob(value, &mut length);
+        if p.is_null() {
+            return Ok(MetaValue::Blob(Vec::new()));
+        }
+        Ok(MetaValue::Blob(std::slice::from_raw_parts(p as *const u8, length).to_vec()))
+    } else if value_type == s::vips_array_int_get_type() {
+        let mut n: i32 = 0;
+        let p = s::vips_value_get_array_int(value, &mut n);
+        if p.is_null() {
+            return Ok(MetaValue::ArrayInt(Vec::new()));
+        }
+        Ok(MetaValue::ArrayInt(std::slice::from_raw_parts(p, n as usize).to_vec()))
+    } else if value_type == s::vips_array_double_get_type() {
+        let mut n: i32 = 0;
+        let p = s::vips_value_get_array_double(value, &mut n);
+        if p.is_null() {
+            return Ok(MetaValue::ArrayDouble(Vec::new()));
+        }
+        Ok(MetaValue::ArrayDouble(std::slice::from_raw_parts(p, n as usize).to_vec()))
+    } else if s::g_type_is_a(value_type, s::vips_image_get_type()) != 0 {
+        let image = s::g_value_get_object(value) as *mut s::VipsImage;
+        // the returned image holds its own ref
+        if !image.is_null() {
+            s::g_object_ref(image as *mut c_void);
+        }
+        Ok(MetaValue::Image(VipsImage::from_c_ptr(image)?))
+    } else {
+        let type_name = CStr::from_ptr(s::g_type_name(value_type)).to_string_lossy();
+        Err(VipsError::new(format!("Unsupported value type `{type_name}`")))
+    }
+}
+
+/// Set the argument `name` of `op` to `value`.
+///
+/// Fails if `op` has no argument of that name. Since bindgen represents Vips
+/// enums and flags as plain integers, integer values are converted to the enum
+/// or flags type the argument expects. Strings are parsed as enum or flags
+/// nicknames, like `"centre"` or `"keep-exif"`.
+pub(crate) unsafe fn set_property(
+    op: *mut s::VipsOperation,
+    name: &str,
+    value: &GValue
+) -> Result<(), VipsError> {
+    let prop_name_c_str = CString::new(name)?;
+
+    let mut pspec: *mut s::GParamSpec = std::ptr::null_mut();
+    let mut argument_class: *mut s::VipsArgumentClass = std::ptr::null_mut();
+    let mut argument_instance: *mut s::VipsArgumentInstance = std::ptr::null_mut();
+    if s::vips_object_get_argument(
+        op as *mut s::VipsObject,
+        prop_name_c_str.as_ptr(),
+        &mut pspec,
+        &mut argument_class,
+        &mut argument_instance
+    ) != 0 {
+        return Err(VipsError::new_from_vips_state()
+            .with_kind(ErrorKind::InvalidArgument)
+            .with_argument(name));
+    }
+
+    let value_type = (*pspec).value_type;
+    let fundamental_type = s::g_type_fundamental(value_type);
+    let is_enum_or_flags = fundamental_type == s::define_G_TYPE_ENUM
+        || fundamental_type == s::define_G_TYPE_FLAGS;
+
+    let raw = if !is_enum_or_flags {
+        None
+    } else if value.value_type() == s::define_G_TYPE_INT {
+        Some(s::g_value_get_int(value.as_ptr()))
+    } else if value.value_type() == s::define_G_TYPE_UINT {
+        Some(s::g_value_get_uint(value.as_ptr()) as i32)
+    } else if value.value_type() == s::define_G_TYPE_STRING {
+        let nick = s::g_value_get_string(value.as_ptr());
+        let raw = if fundamental_type == s::define_G_TYPE_ENUM {
+            s::vips_enum_from_nick(prop_name_c_str.as_ptr(), value_type, nick)
+        } else {
+            s::vips_flags_from_nick(prop_name_c_str.as_ptr(), value_type, nick)
+        };
+        if raw < 0 {
+            return Err(VipsError::new_from_vips_state()
+                .with_kind(ErrorKind::InvalidArgument)
+                .with_argument(name));
+        }
+        Some(raw)
+    } else {
+        None
+    };
+
+    let mut converted = GValue::new(value_type);
+    if let Some(raw) = raw {
+        if fundamental_type == s::define_G_TYPE_ENUM {
+            s::g_value_set_enum(converted.as_mut_ptr(), raw);
+        } else {
+            s::g_value_set_flags(converted.as_mut_ptr(), raw as u32);
+        }
+    } else if s::g_value_transform(value.as_ptr(), converted.as_mut_ptr()) == 0 {
+        return Err(VipsError::new(format!(
+            "Cannot convert {} to {}",
+            CStr::from_ptr(s::g_type_name(value.value_type())).to_string_lossy(),
+            CStr::from_ptr(s::g_type_name(value_type)).to_string_lossy()
+        )).with_kind(ErrorKind::InvalidArgument).with_argument(name));
+    }
+
+    // GObject only warns about values outside the range of the property and
+    // clamps them, so they are rejected here
+    if s::g_param_value_validate(pspec, converted.as_mut_ptr()) != 0 {
+        return Err(VipsError::new("Value is out of range")
+            .with_kind(ErrorKind::InvalidArgument)
+            .with_argument(name));
+    }
+
+    s::g_object_set_property(
+        op as *mut s::GObject,
+        prop_name_c_str.as_ptr(),
+        converted.as_ptr()
+    );
+
+    Ok(())
+}
+
+/// Convert `value` and set it as the argument `name` of `op`. Nothing is set if
+/// `value` converts to `None`, e.g. for unset optional arguments.
+pub(crate) unsafe fn set_argument(
+    op: *mut s::VipsOperation,
+    name: &str,
+    value: &dyn ToGValue
+) -> Result<(), VipsError> {
+    if let Some(g_value) = value.to_gvalue() {
+        set_property(op, name, &g_value)?;
+    }
+
+    Ok(())
+}
+
+/// Get the value of the property `name` of `object`, which must be a
+/// `VipsObject`, converted to `T`.
+pub(crate) unsafe fn get_property<T: FromGValue>(
+    object: *mut s::GObject,
+    name: &str
+) -> Result<T, VipsError> {
+    let prop_name_c_str = CString::new(name)?;
+
+    let mut pspec: *mut s::GParamSpec = std::ptr::null_mut();
+    let mut argument_class: *mut s::VipsArgumentClass = std::ptr::null_mut();
+    let mut argument_instance: *mut s::VipsArgumentInstance = std::ptr::null_mut();
+    if s::vips_object_get_argument(
+        object as *mut s::VipsObject,
+        prop_name_c_str.as_ptr(),
+        &mut pspec,
+        &mut argument_class,
+        &mut argument_instance
+    ) != 0 {
+        return Err(VipsError::new_from_vips_state()
+            .with_kind(ErrorKind::InvalidArgument)
+            .with_argument(name));
+    }
+
+    let mut g_value = GValue::new((*pspec).value_type);
+    s::g_object_get_property(
+        object,
+        prop_name_c_str.as_ptr(),
+        g_value.as_mut_ptr()
+    );
+
+    g_value.get().map_err(|e| e.with_argument(name))
+}
+
+/// The name Vips uses for an identifier passed to the operator macros, which
+/// are written as raw identifiers like `r#match` if they are Rust keywords.
+pub(crate) fn vips_name(ident: &str) -> &str {
+    ident.trim_start_matches("r#")
+}
+
+/// Run the operation `name` with `args` set as its arguments, then call
+/// `outputs` to read the results from the built operation before it is
+/// released. This is for operations whose outputs are not a single `out`
+/// image, which `define_operator!` can not express.
+///
+/// Arguments that convert to `None` are left unset.
+pub(crate) unsafe fn call_operation<T>(
+    name: &str,
+    args: &[(&str, &dyn ToGValue)],
+    outputs: impl FnOnce(*mut s::GObject) -> Result<T, VipsError>
+) -> Result<T, VipsError> {
+    let op_name_c_str = CString::new(name)?;
+    let op = s::vips_operation_new(op_name_c_str.as_ptr());
+    if op.is_null() {
+        return Err(VipsError::new("Could not create operation").with_operation(name));
+    }
+
+    for (arg_name, value) in args {
+        if let Err(e) = set_argument(op, arg_name, *value) {
+            s::g_object_unref(op as *mut c_void);
+            return Err(e.with_operation(name));
+        }
+    }
+
+    let built_op = s::vips_cache_operation_build(op);
+    s::g_object_unref(op as *mut c_void);
+    if built_op.is_null() {
+        return Err(VipsError::new_from_vips_state().with_operation(name));
+    }
+
+    let out = outputs(built_op as *mut s::GObject)
+        .map_err(|e| e.with_operation(name));
+
+    s::vips_object_unref_outputs(built_op as *mut s::VipsObject);
+    s::g_object_unref(built_op as *mut c_void);
+
+    out
+}
+
 #[macro_export]
 macro_rules! parse_operator_input {
     ($self:ident, $op_name:ident, $input_name:literal) => {
@@ -166,7 +732,7 @@ macro_rules! parse_operator_input {
 
             use crate::*;
 
-            let op_name_c_str = CString::new(stringify!($op_name))?;
+            let op_name_c_str = CString::new(vips_name(stringify!($op_name)))?;
             let op = s::vips_operation_new(op_name_c_str.as_ptr());
 
             if op == std::ptr::null_mut() {
@@ -178,11 +744,13 @@ macro_rules! parse_operator_input {
                 None => return Err(VipsError::new("Failed to convert input image to GValue"))
             };
 
+            // the operation takes its own ref to the image, dropping g_value
+            // releases ours
             let prop_name_c_str = CString::new($input_name)?;
             s::g_object_set_property(
                 op as *mut s::GObject,
                 prop_name_c_str.as_ptr(),
-                &g_value
+                g_value.as_ptr()
             );
 
             op
@@ -192,43 +760,31 @@ macro_rules! parse_operator_input {
 
 #[macro_export]
 macro_rules! run_operator {
-    ($op:ident) => {
+    ($op:ident, $op_name:expr) => {
         {
             // run the op
             let cached_op = s::vips_cache_operation_build($op);
             s::g_object_unref($op as *mut c_void);
 
             if cached_op == std::ptr::null_mut() {
-                // TODO: add custom message for context
-                return Err(VipsError::new_from_vips_state());
+                return Err(VipsError::new_from_vips_state().with_operation($op_name));
             }
 
-            // get result
-            let mut g_value: s::GValue = std::mem::zeroed();
-            s::g_value_init(
-                &mut g_value,
-                s::vips_image_get_type()
-            );
+            // get result, which takes its own ref to the image
+            let mut g_value = GValue::new(s::vips_image_get_type());
             let prop_name_c_str = CString::new("out")?;
             s::g_object_get_property(
                 cached_op as *mut s::GObject,
                 prop_name_c_str.as_ptr(),
-                &mut g_value
+                g_value.as_mut_ptr()
             );
-            let out = VipsImage::from_c_ptr(
-                s::g_value_get_object(
-                    &g_value
-                ) as *mut s::VipsImage
-            )?;
-            // g_value_get_object() does not ref the object, so we need to make
-            // a ref for out to hold.
-            s::g_object_ref(out.ptr as *mut c_void);
-            s::g_value_unset(&mut g_value);
+            let out = g_value.get::<VipsImage>();
+            drop(g_value);
 
             s::vips_object_unref_outputs(cached_op as *mut s::VipsObject);
             s::g_object_unref(cached_op as *mut c_void);
 
-            out
+            out?
         }
     }
 }
@@ -238,7 +794,7 @@ macro_rules! run_operator {
 /// ```ignore
 /// define_operator!(conv, struct ConvArgs<'a> {
 ///     mask: &'a VipsImage,
-///     precision: Option<vips_sys::VipsPrecision>,
+///     precision: Option<Precision>,
 ///     layers: Option<u32>,
 ///     cluster: Option<u32>
 /// });
@@ -254,8 +810,9 @@ macro_rules! run_operator {
 /// ```
 /// 
 /// The name of the argument struct will be discarded and the resulting struct
-/// will always have the name `OpArgs`. The resulting operator can then be
-/// called this way:
+/// will always have the name `OpArgs`. Doc comments on the struct document
+/// both `OpArgs` and the method. The resulting operator can then be called
+/// this way:
 /// 
 /// ```ignore
 /// let convolved = input_img.conv(conv::OpArgs{ ... });
@@ -274,22 +831,24 @@ macro_rules! define_operator {
             ),*
         }
     ) => {
-        mod $op_name {
+        pub mod $op_name {
             use std::ffi::{CString, c_void};
             use vips_sys as s;
             
             // use super::*;
             use crate::*;
+            use crate::operator::vips_name;
 
             $(#[$meta])*
             pub struct OpArgs $(<$lt>)? {
                 $(
-                    $(#[$param_meta:meta])*
+                    $(#[$param_meta])*
                     $param_vis $param_name : $param_type
                 ),*
             }
 
             impl VipsImage {
+                $(#[$meta])*
                 pub fn $op_name(&self, args: OpArgs) -> Result<VipsImage, VipsError> {
                     unsafe {let op = parse_operator_input!(self, $op_name, $input_name);
                         
@@ -297,19 +856,24 @@ macro_rules! define_operator {
                         $(
                             let g_value = args.$param_name.to_gvalue();
                             match g_value {
+                                // v is unset when it goes out of scope, which
+                                // releases what it refs once the operation
+                                // has taken its own copy
                                 Some(v) => {
-                                    let prop_name_c_str = CString::new(stringify!($param_name))?;
-                                    s::g_object_set_property(
-                                        op as *mut s::GObject,
-                                        prop_name_c_str.as_ptr(),
+                                    if let Err(e) = crate::operator::set_property(
+                                        op,
+                                        vips_name(stringify!($param_name)),
                                         &v
-                                    );
+                                    ) {
+                                        s::g_object_unref(op as *mut c_void);
+                                        return Err(e.with_operation(vips_name(stringify!($op_name))));
+                                    }
                                 },
                                 _ => {} // if it's not Some(v), this arg wasn't there
                             };
                         )*
 
-                        let out = run_operator!(op);
+                        let out = run_operator!(op, vips_name(stringify!($op_name)));
 
                         return Ok(out);
                     }
@@ -322,19 +886,18 @@ macro_rules! define_operator {
         $op_name:ident,
         $input_name:literal
     ) => {
-        mod $op_name {
-            use super::*;
-
+        pub mod $op_name {
             use std::ffi::{CString, c_void};
             use vips_sys as s;
 
             use crate::*;
+            use crate::operator::vips_name;
 
             impl VipsImage {
                 pub fn $op_name(&self) -> Result<VipsImage, VipsError> {
                     unsafe {
                         let op = parse_operator_input!(self, $op_name, $input_name);
-                        let out = run_operator!(op);
+                        let out = run_operator!(op, vips_name(stringify!($op_name)));
                         return Ok(out);
                     }
                 }
@@ -351,49 +914,385 @@ macro_rules! define_operator {
     };
 }
 
-// === CONVOLUTION OPERATORS ===
+/// Define an operator with several outputs, like:
+///
+/// ```ignore
+/// define_multi_output_operator!(find_trim, struct Args<'a> {
+///     pub threshold: Option<f64>,
+///     pub background: Option<&'a [f64]>
+/// } => struct Output {
+///     pub left: i32,
+///     pub top: i32,
+///     pub width: i32,
+///     pub height: i32
+/// });
+/// ```
+///
+/// As with `define_operator!`, a custom input parameter name can be passed as
+/// the second argument and the argument struct is called `OpArgs`. The outputs
+/// are returned in a struct called `OpOutput`, with one field per output
+/// argument of the operation. Output types must implement `FromGValue`.
+///
+/// Operators without arguments leave out the argument struct:
+///
+/// ```ignore
+/// define_multi_output_operator!(profile => struct Output {
+///     pub columns: VipsImage,
+///     pub rows: VipsImage
+/// });
+/// ```
+#[macro_export]
+macro_rules! define_multi_output_operator {
+    // custom input name, arg struct definition
+    (
+        $op_name:ident,
+        $input_name:literal,
+        $(#[$meta:meta])*
+        $struct_vis:vis struct $param_struct_name:ident $(<$lt:lifetime>)? {
+            $(
+                $(#[$param_meta:meta])*
+                $param_vis:vis $param_name:ident: $param_type:ty
+            ),*
+        } => $(#[$out_meta:meta])*
+        $out_vis:vis struct $out_struct_name:ident {
+            $(
+                $(#[$out_field_meta:meta])*
+                $out_field_vis:vis $out_name:ident: $out_type:ty
+            ),*
+        }
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            $(#[$meta])*
+            pub struct OpArgs $(<$lt>)? {
+                $(
+                    $(#[$param_meta])*
+                    $param_vis $param_name : $param_type
+                ),*
+            }
+
+            $(#[$out_meta])*
+            pub struct OpOutput {
+                $(
+                    $(#[$out_field_meta])*
+                    pub $out_name : $out_type
+                ),*
+            }
+
+            impl VipsImage {
+                $(#[$meta])*
+                pub fn $op_name(&self, args: OpArgs) -> Result<OpOutput, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[
+                        ($input_name, self),
+                        $( (vips_name(stringify!($param_name)), &args.$param_name) ),*
+                    ];
+
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            Ok(OpOutput {
+                                $( $out_name: get_property(op, vips_name(stringify!($out_name)))? ),*
+                            })
+                        })
+                    }
+                }
+            }
+        }
+    };
+    // custom input name with no args
+    (
+        $op_name:ident,
+        $input_name:literal =>
+        $(#[$out_meta:meta])*
+        $out_vis:vis struct $out_struct_name:ident {
+            $(
+                $(#[$out_field_meta:meta])*
+                $out_field_vis:vis $out_name:ident: $out_type:ty
+            ),*
+        }
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            $(#[$out_meta])*
+            pub struct OpOutput {
+                $(
+                    $(#[$out_field_meta])*
+                    pub $out_name : $out_type
+                ),*
+            }
+
+            impl VipsImage {
+                $(#[$out_meta])*
+                pub fn $op_name(&self) -> Result<OpOutput, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[($input_name, self)];
+
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            Ok(OpOutput {
+                                $( $out_name: get_property(op, vips_name(stringify!($out_name)))? ),*
+                            })
+                        })
+                    }
+                }
+            }
+        }
+    };
+    // using the standard input name "in", without args...
+    ( $op_name:ident => $($output_struct_def:tt)* ) => {
+        define_multi_output_operator!($op_name, "in" => $($output_struct_def)*);
+    };
+    // and with
+    ( $op_name:ident, $($param_struct_def:tt)* ) => {
+        define_multi_output_operator!($op_name, "in", $($param_struct_def)*);
+    };
+}
+
+/// Define an operator taking an array of images as its input, like:
+///
+/// ```ignore
+/// define_array_operator!(bandrank, struct Args {
+///     pub index: Option<i32>
+/// });
+/// ```
+///
+/// The operator becomes an associated function taking the images as its first
+/// argument:
+///
+/// ```ignore
+/// let median = VipsImage::bandrank(&[&a, &b, &c], bandrank::OpArgs{index: None});
+/// ```
+///
+/// The input parameter name, the argument struct and its naming work like in
+/// `define_operator!`.
+#[macro_export]
+macro_rules! define_array_operator {
+    // custom input name, arg struct definition
+    (
+        $op_name:ident,
+        $input_name:literal,
+        $(#[$meta:meta])*
+        $struct_vis:vis struct $param_struct_name:ident $(<$lt:lifetime>)? {
+            $(
+                $(#[$param_meta:meta])*
+                $param_vis:vis $param_name:ident: $param_type:ty
+            ),*
+        }
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            $(#[$meta])*
+            pub struct OpArgs $(<$lt>)? {
+                $(
+                    $(#[$param_meta])*
+                    $param_vis $param_name : $param_type
+                ),*
+            }
+
+            impl VipsImage {
+                $(#[$meta])*
+                pub fn $op_name(images: &[&VipsImage], args: OpArgs) -> Result<VipsImage, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[
+                        ($input_name, &images),
+                        $( (vips_name(stringify!($param_name)), &args.$param_name) ),*
+                    ];
+
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            get_property(op, "out")
+                        })
+                    }
+                }
+            }
+        }
+    };
+    // custom input name with no args
+    (
+        $op_name:ident,
+        $input_name:literal
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            impl VipsImage {
+                pub fn $op_name(images: &[&VipsImage]) -> Result<VipsImage, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[($input_name, &images)];
+
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            get_property(op, "out")
+                        })
+                    }
+                }
+            }
+        }
+    };
+    // using the standard input name "in", with args...
+    ( $op_name:ident, $($param_struct_def:tt)* ) => {
+        define_array_operator!($op_name, "in", $($param_struct_def)*);
+    };
+    // and without...
+    ( $op_name:ident ) => {
+        define_array_operator!($op_name, "in");
+    };
+}
+
+/// Define an operator that makes an image without taking one, like:
+///
+/// ```ignore
+/// define_create_operator!(black, struct Args {
+///     pub width: i32,
+///     pub height: i32,
+///     pub bands: Option<i32>
+/// });
+/// ```
+///
+/// The operator becomes an associated function taking only the arguments:
+///
+/// ```ignore
+/// let img = VipsImage::black(black::OpArgs{width: 64, height: 64, bands: None});
+/// ```
+///
+/// Operators with more outputs than the `out` image name them in an output
+/// struct as in `define_multi_output_operator!`.
+#[macro_export]
+macro_rules! define_create_operator {
+    // arg struct and output struct definition
+    (
+        $op_name:ident,
+        $(#[$meta:meta])*
+        $struct_vis:vis struct $param_struct_name:ident $(<$lt:lifetime>)? {
+            $(
+                $(#[$param_meta:meta])*
+                $param_vis:vis $param_name:ident: $param_type:ty
+            ),*
+        } => $(#[$out_meta:meta])*
+        $out_vis:vis struct $out_struct_name:ident {
+            $(
+                $(#[$out_field_meta:meta])*
+                $out_field_vis:vis $out_name:ident: $out_type:ty
+            ),*
+        }
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            $(#[$meta])*
+            pub struct OpArgs $(<$lt>)? {
+                $(
+                    $(#[$param_meta])*
+                    $param_vis $param_name : $param_type
+                ),*
+            }
+
+            $(#[$out_meta])*
+            pub struct OpOutput {
+                $(
+                    $(#[$out_field_meta])*
+                    pub $out_name : $out_type
+                ),*
+            }
+
+            impl VipsImage {
+                $(#[$meta])*
+                pub fn $op_name(args: OpArgs) -> Result<OpOutput, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[
+                        $( (vips_name(stringify!($param_name)), &args.$param_name) ),*
+                    ];
+
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            Ok(OpOutput {
+                                $( $out_name: get_property(op, vips_name(stringify!($out_name)))? ),*
+                            })
+                        })
+                    }
+                }
+            }
+        }
+    };
+    // arg struct definition
+    (
+        $op_name:ident,
+        $(#[$meta:meta])*
+        $struct_vis:vis struct $param_struct_name:ident $(<$lt:lifetime>)? {
+            $(
+                $(#[$param_meta:meta])*
+                $param_vis:vis $param_name:ident: $param_type:ty
+            ),*
+        }
+    ) => {
+        pub mod $op_name {
+            use crate::*;
+            use crate::operator::{ToGValue, call_operation, get_property, vips_name};
+
+            $(#[$meta])*
+            pub struct OpArgs $(<$lt>)? {
+                $(
+                    $(#[$param_meta])*
+                    $param_vis $param_name : $param_type
+                ),*
+            }
 
-// TODO: move this to its own module
+            impl VipsImage {
+                $(#[$meta])*
+                pub fn $op_name(args: OpArgs) -> Result<VipsImage, VipsError> {
+                    let op_args: &[(&str, &dyn ToGValue)] = &[
+                        $( (vips_name(stringify!($param_name)), &args.$param_name) ),*
+                    ];
 
-define_operator!(conv, struct Args<'a> {
-    pub mask: &'a VipsImage,
-    pub precision: Option<vips_sys::VipsPrecision>,
-    pub layers: Option<u32>,
-    pub cluster: Option<u32>
-});
+                    unsafe {
+                        call_operation(vips_name(stringify!($op_name)), op_args, |op| {
+                            get_property(op, "out")
+                        })
+                    }
+                }
+            }
+        }
+    };
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ensure_vips_init_or_exit;
-    use std::path::PathBuf;
+    use crate::{ensure_vips_init_or_exit, Interesting};
 
     #[test]
-    fn convolve() {
+    fn gvalue() {
         ensure_vips_init_or_exit();
 
-        let img = VipsImage::new_from_file(PathBuf::from("./data/test.jpg"))
-            .expect("Image could not be created from file");
-        assert_ne!(img.ptr, std::ptr::null_mut());
+        let value = 42.to_gvalue().expect("Could not convert integer");
+        assert_eq!(value.get::<i32>().expect("Could not read integer"), 42);
+        assert_eq!(value.get::<f64>().expect("Could not read number"), 42.);
 
-        let kernel_size = 64;
-        let kernel = VipsImage::new_matrix(kernel_size, kernel_size)
-            .expect("Could not create kernel");
+        let value = "centre".to_gvalue().expect("Could not convert string");
+        assert_eq!(value.get::<String>().expect("Could not read string"), "centre");
 
-        let kernel_n_px: f64 = (kernel_size * kernel_size).try_into().unwrap();
-        let kernel_v = 16f64 / kernel_n_px; // TODO: figure out why the kernel needs sum ~16
-        unsafe {
-            vips_sys::vips_draw_rect1(kernel.ptr,
-                kernel_v,
-                0, 0, kernel_size, kernel_size, 0);
-        }
-        let convolved = img.conv(conv::OpArgs{
-            mask: &kernel,
-            precision: None, layers: None, cluster: None
-        })
-            .expect("Error while applying operation `conv`");
-
-        convolved.write_to_file(PathBuf::from("./data/test_convolved.jpg"))
-            .expect("Could not save image to file");
+        let value = [1., 2.].as_slice().to_gvalue().expect("Could not convert array");
+        assert_eq!(value.get::<Vec<f64>>().expect("Could not read array"), vec![1., 2.]);
+        assert!(value.get::<VipsImage>().is_err());
+
+        let value = Interesting::Attention.to_gvalue().expect("Could not convert enum");
+        assert_eq!(value.get::<Interesting>().expect("Could not read enum"), Interesting::Attention);
+
+        let img = VipsImage::new_matrix_from_array(1, 1, &[1.])
+            .expect("Could not create matrix");
+        let ref_count = || unsafe { (*(img.ptr as *mut s::GObject)).ref_count };
+        let refs = ref_count();
+
+        // the value refs the images it holds and releases them when dropped
+        let value = [&img, &img].as_slice().to_gvalue().expect("Could not convert images");
+        assert_eq!(ref_count(), refs + 2);
+        let images = value.get::<Vec<VipsImage>>().expect("Could not read images");
+        assert_eq!(images.len(), 2);
+        drop(value);
+        drop(images);
+        assert_eq!(ref_count(), refs);
     }
 }
@@ -0,0 +1,292 @@
+//! Statistics of the pixels of an image, returned as Rust values
+
+use crate::*;
+use crate::operator::{call_operation, get_property};
+
+// =============================================================================
+// === Results =================================================================
+// =============================================================================
+
+/// The result of `min` or `max`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extremum {
+    /// The smallest or largest value across all bands
+    pub value: f64,
+    /// Horizontal position of `value`
+    pub x: i32,
+    /// Vertical position of `value`
+    pub y: i32,
+    /// The `size` smallest or largest values, sorted with the most extreme
+    /// value first
+    pub out_array: Vec<f64>,
+    /// Horizontal positions of the values in `out_array`
+    pub x_array: Vec<i32>,
+    /// Vertical positions of the values in `out_array`
+    pub y_array: Vec<i32>
+}
+
+/// Statistics of one band or of all bands of an image, see `stats`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BandStats {
+    pub min: f64,
+    pub max: f64,
+    pub sum: f64,
+    /// Sum of squares
+    pub sum2: f64,
+    pub mean: f64,
+    /// Standard deviation
+    pub deviation: f64,
+    /// Horizontal position of `min`
+    pub x_min: i32,
+    /// Vertical position of `min`
+    pub y_min: i32,
+    /// Horizontal position of `max`
+    pub x_max: i32,
+    /// Vertical position of `max`
+    pub y_max: i32
+}
+
+/// The result of `stats`
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatsMatrix {
+    /// Statistics across all bands
+    pub all: BandStats,
+    /// Statistics of each band
+    pub bands: Vec<BandStats>
+}
+
+impl StatsMatrix {
+    /// Read the matrix image made by `vips_stats()`, which has one row of ten
+    /// columns for all bands followed by one row per band.
+    fn from_image(mut image: VipsImage) -> Result<StatsMatrix, VipsError> {
+        const COLUMNS: usize = 10;
+
+        let (width, height) = (image.width(), image.height());
+        let values = image.as_slice::<f64>()?;
+        if width != COLUMNS || values.len() < COLUMNS {
+            return Err(VipsError::new(format!(
+                "Stats matrix has unexpected size {width}x{height}"
+            )));
+        }
+
+        let mut rows = values.chunks_exact(COLUMNS).map(|row| BandStats {
+            min: row[0],
+            max: row[1],
+            sum: row[2],
+            sum2: row[3],
+            mean: row[4],
+            deviation: row[5],
+            x_min: row[6] as i32,
+            y_min: row[7] as i32,
+            x_max: row[8] as i32,
+            y_max: row[9] as i32
+        });
+
+        match rows.next() {
+            Some(all) => Ok(StatsMatrix { all, bands: rows.collect() }),
+            None => Err(VipsError::new("Stats matrix is empty"))
+        }
+    }
+}
+
+// =============================================================================
+// === VipsImage ===============================================================
+// =============================================================================
+
+impl VipsImage {
+    /// Average of all pixels in all bands
+    pub fn avg(&self) -> Result<f64, VipsError> {
+        unsafe {
+            call_operation("avg", &[("in", self)], |op| {
+                get_property(op, "out")
+            })
+        }
+    }
+
+    /// Standard deviation of all pixels in all bands
+    pub fn deviate(&self) -> Result<f64, VipsError> {
+        unsafe {
+            call_operation("deviate", &[("in", self)], |op| {
+                get_property(op, "out")
+            })
+        }
+    }
+
+    /// The smallest value in all bands and where it is. Pass `size` to also
+    /// find the `size` smallest values.
+    pub fn min(&self, size: Option<i32>) -> Result<Extremum, VipsError> {
+        self.extremum("min", size)
+    }
+
+    /// The largest value in all bands and where it is. Pass `size` to also
+    /// find the `size` largest values.
+    pub fn max(&self, size: Option<i32>) -> Result<Extremum, VipsError> {
+        self.extremum("max", size)
+    }
+
+    /// Minimum, maximum, sum, mean and deviation of each band and of all bands
+    pub fn stats(&self) -> Result<StatsMatrix, VipsError> {
+        let matrix = unsafe {
+            call_operation("stats", &[("in", self)], |op| {
+                get_property(op, "out")
+            })
+        }?;
+
+        StatsMatrix::from_image(matrix)
+    }
+
+    /// The values of all bands of the pixel at `x`, `y`
+    pub fn getpoint(&self, x: i32, y: i32) -> Result<Vec<f64>, VipsError> {
+        unsafe {
+            call_operation("getpoint", &[("in", self), ("x", &x), ("y", &y)], |op| {
+                get_property(op, "out_array")
+            })
+        }
+    }
+
+    /// Measure the average of each band in the patches of a colour chart with
+    /// `h` patches across and `v` patches down. Returns one row of band
+    /// averages per patch, going across the chart and then down.
+    ///
+    /// Only the centre of each patch is measured, so the borders between
+    /// patches do not affect the result.
+    pub fn measure(&self, h: i32, v: i32) -> Result<Vec<Vec<f64>>, VipsError> {
+        let mut matrix: VipsImage = unsafe {
+            call_operation("measure", &[("in", self), ("h", &h), ("v", &v)], |op| {
+                get_property(op, "out")
+            })
+        }?;
+
+        let width = matrix.width();
+        let values = matrix.as_slice::<f64>()?;
+        Ok(values.chunks_exact(width).map(|row| row.to_vec()).collect())
+    }
+
+    fn extremum(&self, op_name: &str, size: Option<i32>) -> Result<Extremum, VipsError> {
+        unsafe {
+            call_operation(op_name, &[("in", self), ("size", &size)], |op| {
+                Ok(Extremum {
+                    value: get_property(op, "out")?,
+                    x: get_property(op, "x")?,
+                    y: get_property(op, "y")?,
+                    out_array: get_property(op, "out_array")?,
+                    x_array: get_property(op, "x_array")?,
+                    y_array: get_property(op, "y_array")?
+                })
+            })
+        }
+    }
+}
+
+// =============================================================================
+// === Operators ===============================================================
+// =============================================================================
+
+define_multi_output_operator!(find_trim, struct Args<'a> {
+    /// How much pixels may differ from the background and still be trimmed
+    pub threshold: Option<f64>,
+    /// The background colour, by default the colour of the top left pixel
+    pub background: Option<&'a [f64]>,
+    /// Trim white line art on a black background instead
+    pub line_art: Option<bool>
+} => struct Output {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32
+});
+
+define_multi_output_operator!(profile => struct Output {
+    /// For each column, the position of the first non-zero pixel from the top
+    pub columns: VipsImage,
+    /// For each row, the position of the first non-zero pixel from the left
+    pub rows: VipsImage
+});
+
+define_multi_output_operator!(project => struct Output {
+    /// Sum of each column
+    pub columns: VipsImage,
+    /// Sum of each row
+    pub rows: VipsImage
+});
+
+// =============================================================================
+// === Tests ===================================================================
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics() {
+        ensure_vips_init_or_exit();
+        let _errors = crate::error::lock_error_buffer();
+
+        let img = VipsImage::new_matrix_from_array(2, 2, &[1., 2., 3., 4.])
+            .expect("Could not create matrix");
+
+        assert_eq!(img.avg().expect("Could not get average"), 2.5);
+        assert!((img.deviate().expect("Could not get deviation") - 1.290994).abs() < 1e-6);
+
+        let min = img.min(None).expect("Could not get minimum");
+        assert_eq!((min.value, min.x, min.y), (1., 0, 0));
+        let max = img.max(Some(2)).expect("Could not get maximum");
+        assert_eq!((max.value, max.x, max.y), (4., 1, 1));
+        assert_eq!(max.out_array, vec![4., 3.]);
+
+        let stats = img.stats().expect("Could not get stats");
+        assert_eq!(stats.bands.len(), 1);
+        assert_eq!(stats.all.sum, 10.);
+        assert_eq!(stats.all.sum2, 30.);
+        assert_eq!((stats.bands[0].x_max, stats.bands[0].y_max), (1, 1));
+
+        assert_eq!(img.getpoint(1, 0).expect("Could not get point"), vec![2.]);
+        match img.getpoint(5, 5) {
+            Ok(_) => panic!("Getting a point outside the image should error"),
+            Err(e) => assert_eq!(e.operation(), Some("getpoint"))
+        }
+    }
+
+    #[test]
+    fn measure() {
+        ensure_vips_init_or_exit();
+
+        let img = VipsImage::new_matrix_from_array(4, 4, &[
+            1., 1., 2., 2.,
+            1., 1., 2., 2.,
+            3., 3., 4., 4.,
+            3., 3., 4., 4.
+        ]).expect("Could not create matrix");
+
+        let patches = img.measure(2, 2).expect("Could not measure chart");
+        assert_eq!(patches, vec![vec![1.], vec![2.], vec![3.], vec![4.]]);
+    }
+
+    #[test]
+    fn multi_output() {
+        ensure_vips_init_or_exit();
+
+        // find_trim median-filters the image first, so the block has to be
+        // larger than a few pixels
+        let mut values = vec![0.; 8 * 8];
+        for y in 2..6 {
+            for x in 2..6 {
+                values[y * 8 + x] = 5.;
+            }
+        }
+        let img = VipsImage::new_matrix_from_array(8, 8, &values)
+            .expect("Could not create matrix");
+
+        let trim = img.find_trim(find_trim::OpArgs{
+            threshold: Some(1.), background: Some(&[0.]), line_art: None
+        }).expect("Could not find trim");
+        assert_eq!((trim.left, trim.top, trim.width, trim.height), (2, 2, 4, 4));
+
+        let projection = img.project().expect("Could not project image");
+        assert_eq!((projection.columns.width(), projection.rows.height()), (8, 8));
+
+        let profile = img.profile().expect("Could not get profile");
+        assert_eq!(profile.columns.width(), 8);
+    }
+}
@@ -1,6 +1,6 @@
 /// Utilities for interacting with Vips
 
-use std::ffi::{CString, CStr};
+use std::ffi::CString;
 use std::sync::Once;
 
 use crate::*;
@@ -28,13 +28,7 @@ pub fn vips_init() -> Result<(), VipsError> {
     };
 
     if unsafe { vips_sys::vips_init(c_argv_0.as_ptr()) } != 0 {
-        let err_descr = unsafe {
-            CStr::from_ptr(vips_sys::vips_error_buffer())
-        };
-        match err_descr.to_str() {
-            Ok(err_str) => Err(VipsError::new(err_str)),
-            Err(e) => Err(VipsError::new(format!("Error parsing Vips error buffer: `{e}`")))
-        }
+        Err(VipsError::new_from_vips_state())
     } else {
         Ok(())
     }
@@ -103,3 +97,39 @@ impl Drop for VipsHandle {
         vips_shutdown();
     }
 }
+
+// =============================================================================
+// === SIGNALS =================================================================
+// =============================================================================
+
+/// Connect `handler` to the signal `signal` emitted by the GObject `instance`.
+///
+/// `handler` must be an `extern "C"` function with the signature glib expects
+/// for this signal, transmuted to a bare `unsafe extern "C" fn()`. `data` is
+/// passed as the last argument on each emission. If `destroy` is given, it is
+/// called with `data` once the handler is disconnected, which at the latest
+/// happens when `instance` is finalized.
+pub(crate) unsafe fn signal_connect(
+    instance: *mut std::ffi::c_void,
+    signal: &str,
+    handler: unsafe extern "C" fn(),
+    data: *mut std::ffi::c_void,
+    destroy: vips_sys::GClosureNotify
+) -> Result<(), VipsError> {
+    let signal_c_str = CString::new(signal)?;
+
+    let handler_id = vips_sys::g_signal_connect_data(
+        instance,
+        signal_c_str.as_ptr(),
+        Some(handler),
+        data,
+        destroy,
+        0
+    );
+
+    if handler_id == 0 {
+        return Err(VipsError::new(format!("Could not connect to signal `{signal}`")));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,636 @@
+//! Generates `src/generated.rs` of `vips-rs`, which wraps every operation of
+//! the installed libvips that is not wrapped by hand.
+//!
+//! Run it from anywhere in the workspace:
+//!
+//! ```sh
+//! cargo run -p vips-gen
+//! ```
+//!
+//! The output is committed, so building `vips-rs` does not need libvips
+//! introspection. Operations are skipped if `vips-rs` already has a method of
+//! the same name, so moving an operation to a hand-written wrapper only needs
+//! a rerun.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::{CStr, CString, c_char, c_int, c_void};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use vips_sys as s;
+
+// =============================================================================
+// === Introspection ===========================================================
+// =============================================================================
+
+/// An argument of an operation as reported by Vips
+struct Argument {
+    name: String,
+    blurb: String,
+    value_type: s::GType,
+    flags: u32
+}
+
+impl Argument {
+    fn has_flag(&self, flag: s::VipsArgumentFlags) -> bool {
+        self.flags & flag != 0
+    }
+
+    fn is_input(&self) -> bool { self.has_flag(s::VipsArgumentFlags_VIPS_ARGUMENT_INPUT) }
+    fn is_output(&self) -> bool { self.has_flag(s::VipsArgumentFlags_VIPS_ARGUMENT_OUTPUT) }
+    fn is_required(&self) -> bool { self.has_flag(s::VipsArgumentFlags_VIPS_ARGUMENT_REQUIRED) }
+}
+
+/// An operation as reported by Vips
+struct Operation {
+    nickname: String,
+    description: String,
+    arguments: Vec<Argument>
+}
+
+unsafe fn c_string(p: *const c_char) -> String {
+    if p.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(p).to_string_lossy().into_owned()
+}
+
+unsafe extern "C" fn collect_type(g_type: s::GType, types: *mut c_void) -> *mut c_void {
+    (*(types as *mut Vec<s::GType>)).push(g_type);
+    std::ptr::null_mut()
+}
+
+/// All non-abstract, non-deprecated operations except loaders and savers,
+/// which `vips-rs` wraps in `foreign`
+unsafe fn operations() -> Vec<Operation> {
+    let mut types: Vec<s::GType> = Vec::new();
+    s::vips_type_map_all(
+        s::vips_operation_get_type(),
+        Some(collect_type),
+        &mut types as *mut Vec<s::GType> as *mut c_void
+    );
+
+    let mut operations: Vec<Operation> = types.into_iter()
+        .filter(|&t| s::g_type_test_flags(t, s::GTypeFlags_G_TYPE_FLAG_ABSTRACT) == 0)
+        .filter(|&t| s::g_type_is_a(t, s::vips_foreign_get_type()) == 0)
+        .filter_map(|t| operation(&c_string(s::vips_nickname_find(t))))
+        .collect();
+
+    operations.sort_by(|a, b| a.nickname.cmp(&b.nickname));
+    operations.dedup_by(|a, b| a.nickname == b.nickname);
+    operations
+}
+
+unsafe fn operation(nickname: &str) -> Option<Operation> {
+    let nickname_c_str = CString::new(nickname).ok()?;
+    let op = s::vips_operation_new(nickname_c_str.as_ptr());
+    if op.is_null() {
+        return None;
+    }
+
+    let deprecated = s::vips_operation_get_flags(op)
+        & s::VipsOperationFlags_VIPS_OPERATION_DEPRECATED != 0;
+
+    let mut names: *mut *const c_char = std::ptr::null_mut();
+    let mut flags: *mut c_int = std::ptr::null_mut();
+    let mut n_args: c_int = 0;
+    let mut arguments = Vec::new();
+    if s::vips_object_get_args(op as *mut s::VipsObject, &mut names, &mut flags, &mut n_args) == 0 {
+        for i in 0..n_args.max(0) as usize {
+            let name = c_string(*names.add(i));
+            let name_c_str = CString::new(name.as_str()).ok()?;
+
+            let mut pspec: *mut s::GParamSpec = std::ptr::null_mut();
+            let mut argument_class: *mut s::VipsArgumentClass = std::ptr::null_mut();
+            let mut argument_instance: *mut s::VipsArgumentInstance = std::ptr::null_mut();
+            if s::vips_object_get_argument(
+                op as *mut s::VipsObject,
+                name_c_str.as_ptr(),
+                &mut pspec,
+                &mut argument_class,
+                &mut argument_instance
+            ) != 0 {
+                continue;
+            }
+
+            arguments.push(Argument {
+                name,
+                blurb: c_string(s::g_param_spec_get_blurb(pspec)),
+                value_type: (*pspec).value_type,
+                flags: *flags.add(i) as u32
+            });
+        }
+    }
+
+    let description = c_string(s::vips_object_get_description(op as *mut s::VipsObject));
+    s::g_object_unref(op as *mut c_void);
+
+    if deprecated {
+        return None;
+    }
+
+    Some(Operation { nickname: nickname.to_string(), description, arguments })
+}
+
+// =============================================================================
+// === Types ===================================================================
+// =============================================================================
+
+/// The kinds of values the wrappers can pass to and read from Vips
+#[derive(Clone, Copy, PartialEq)]
+enum ValueKind {
+    Bool,
+    Int,
+    UInt64,
+    Double,
+    String,
+    Image,
+    ArrayDouble,
+    ArrayInt,
+    ArrayImage,
+    Blob,
+    Source,
+    Target,
+    Enum(s::GType),
+    Flags
+}
+
+unsafe fn value_kind(g_type: s::GType) -> Option<ValueKind> {
+    let fundamental = s::g_type_fundamental(g_type);
+
+    if g_type == s::define_G_TYPE_BOOLEAN {
+        Some(ValueKind::Bool)
+    } else if g_type == s::define_G_TYPE_INT {
+        Some(ValueKind::Int)
+    } else if g_type == s::define_G_TYPE_UINT64 {
+        Some(ValueKind::UInt64)
+    } else if g_type == s::define_G_TYPE_DOUBLE {
+        Some(ValueKind::Double)
+    } else if g_type == s::define_G_TYPE_STRING {
+        Some(ValueKind::String)
+    } else if fundamental == s::define_G_TYPE_ENUM {
+        Some(ValueKind::Enum(g_type))
+    } else if fundamental == s::define_G_TYPE_FLAGS {
+        Some(ValueKind::Flags)
+    } else if g_type == s::vips_array_double_get_type() {
+        Some(ValueKind::ArrayDouble)
+    } else if g_type == s::vips_array_int_get_type() {
+        Some(ValueKind::ArrayInt)
+    } else if g_type == s::vips_array_image_get_type() {
+        Some(ValueKind::ArrayImage)
+    } else if g_type == s::vips_blob_get_type() {
+        Some(ValueKind::Blob)
+    } else if s::g_type_is_a(g_type, s::vips_image_get_type()) != 0 {
+        Some(ValueKind::Image)
+    } else if s::g_type_is_a(g_type, s::vips_source_get_type()) != 0 {
+        Some(ValueKind::Source)
+    } else if s::g_type_is_a(g_type, s::vips_target_get_type()) != 0 {
+        Some(ValueKind::Target)
+    } else {
+        None
+    }
+}
+
+// =============================================================================
+// === Generator ===============================================================
+// =============================================================================
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+    "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop",
+    "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield"
+];
+
+const HAND_WRITTEN: &str = "wrapped by hand";
+
+struct Generator {
+    /// Names of methods `vips-rs` already has, which must not be generated
+    taken: BTreeSet<String>,
+    /// Rust names of the enums in `enums.rs`, by the name of their C type
+    known_enums: BTreeMap<String, String>,
+    /// Enums the generated operators use that need to be generated as well
+    new_enums: BTreeMap<String, s::GType>,
+    skipped: Vec<(String, &'static str)>
+}
+
+impl Generator {
+    fn enum_name(&mut self, g_type: s::GType) -> String {
+        let c_name = unsafe { c_string(s::g_type_name(g_type)) };
+        if let Some(name) = self.known_enums.get(&c_name) {
+            return name.clone();
+        }
+
+        let name = c_name.strip_prefix("Vips").unwrap_or(&c_name).to_string();
+        self.new_enums.insert(c_name, g_type);
+        name
+    }
+
+    fn input_type(&mut self, kind: ValueKind) -> String {
+        match kind {
+            ValueKind::Bool => "bool".to_string(),
+            ValueKind::Int => "i32".to_string(),
+            ValueKind::UInt64 => "u64".to_string(),
+            ValueKind::Double => "f64".to_string(),
+            ValueKind::String => "&'a str".to_string(),
+            ValueKind::Image => "&'a VipsImage".to_string(),
+            ValueKind::ArrayDouble => "&'a [f64]".to_string(),
+            ValueKind::ArrayInt => "&'a [i32]".to_string(),
+            ValueKind::ArrayImage => "&'a [&'a VipsImage]".to_string(),
+            ValueKind::Blob => "&'a [u8]".to_string(),
+            ValueKind::Source => "&'a VipsSource".to_string(),
+            ValueKind::Target => "&'a VipsTarget".to_string(),
+            ValueKind::Enum(g_type) => self.enum_name(g_type),
+            // set_property converts integers to the flags type
+            ValueKind::Flags => "i32".to_string()
+        }
+    }
+
+    fn output_type(&mut self, kind: ValueKind) -> Option<String> {
+        match kind {
+            ValueKind::Bool => Some("bool".to_string()),
+            ValueKind::Int => Some("i32".to_string()),
+            ValueKind::Double => Some("f64".to_string()),
+            ValueKind::String => Some("String".to_string()),
+            ValueKind::Image => Some("VipsImage".to_string()),
+            ValueKind::ArrayDouble => Some("Vec<f64>".to_string()),
+            ValueKind::ArrayInt => Some("Vec<i32>".to_string()),
+            ValueKind::Blob => Some("Vec<u8>".to_string()),
+            ValueKind::Enum(g_type) => Some(self.enum_name(g_type)),
+            _ => None
+        }
+    }
+
+    /// Emit the macro call wrapping `op`, or the reason it can not be wrapped
+    fn operator(&mut self, op: &Operation) -> Result<String, &'static str> {
+        if self.taken.contains(&op.nickname) {
+            return Err(HAND_WRITTEN);
+        }
+        let op_name = identifier(&op.nickname).ok_or("name can not be an identifier")?;
+
+        let arguments: Vec<&Argument> = op.arguments.iter()
+            .filter(|a| !a.has_flag(s::VipsArgumentFlags_VIPS_ARGUMENT_DEPRECATED))
+            .collect();
+        if arguments.iter().any(|a| a.has_flag(s::VipsArgumentFlags_VIPS_ARGUMENT_MODIFY)) {
+            return Err("modifies its input");
+        }
+
+        // the first required image input becomes `self`, or the associated
+        // function's image array. Operators without one make an image from
+        // their arguments alone.
+        let input = arguments.iter()
+            .filter(|a| a.is_input() && a.is_required())
+            .find_map(|a| match unsafe { value_kind(a.value_type) } {
+                Some(kind @ (ValueKind::Image | ValueKind::ArrayImage)) => Some((*a, kind)),
+                _ => None
+            });
+
+        let mut fields = Vec::new();
+        let inputs = arguments.iter()
+            .filter(|a| a.is_input() && input.is_none_or(|(input, _)| a.name != input.name));
+        for argument in inputs {
+            let kind = unsafe { value_kind(argument.value_type) };
+            let name = identifier(&argument.name);
+            match (kind, name, argument.is_required()) {
+                (Some(kind), Some(name), true) => {
+                    let ty = self.input_type(kind);
+                    fields.push((argument, name, ty));
+                },
+                (Some(kind), Some(name), false) => {
+                    let ty = format!("Option<{}>", self.input_type(kind));
+                    fields.push((argument, name, ty));
+                },
+                (_, _, true) => return Err("unsupported required argument"),
+                // optional arguments we can not express are left at their
+                // defaults
+                (_, _, false) => {}
+            }
+        }
+
+        let mut outputs = Vec::new();
+        for argument in arguments.iter().filter(|a| a.is_output()) {
+            let ty = unsafe { value_kind(argument.value_type) }
+                .and_then(|kind| self.output_type(kind));
+            match (ty, identifier(&argument.name)) {
+                (Some(ty), Some(name)) => outputs.push((argument, name, ty)),
+                _ if argument.is_required() => return Err("unsupported required output"),
+                _ => {}
+            }
+        }
+
+        let single_image_output = outputs.len() == 1
+            && outputs[0].0.name == "out"
+            && outputs[0].2 == "VipsImage";
+
+        let macro_name = match (input.map(|(_, kind)| kind), single_image_output) {
+            (None, _) => "define_create_operator",
+            (Some(ValueKind::ArrayImage), true) => "define_array_operator",
+            (Some(ValueKind::ArrayImage), false) => return Err("array input with several outputs"),
+            (_, true) => "define_operator",
+            (_, false) => "define_multi_output_operator"
+        };
+        if macro_name == "define_create_operator" && fields.is_empty() {
+            return Err("no arguments and no image input");
+        }
+
+        let mut out = String::new();
+        let input_name = match input {
+            Some((input, _)) if input.name != "in" => format!(", \"{}\"", input.name),
+            _ => String::new()
+        };
+
+        // the macros document the generated method with the doc comments of
+        // the first struct, so without one the description is a plain comment
+        let description = sentence(&op.description);
+        let multi_output = macro_name == "define_multi_output_operator"
+            || (macro_name == "define_create_operator" && !single_image_output);
+        if fields.is_empty() && !multi_output {
+            writeln!(out, "// {description}").unwrap();
+            writeln!(out, "{macro_name}!({op_name}{input_name});").unwrap();
+            return Ok(out);
+        }
+
+        write!(out, "{macro_name}!({op_name}{input_name}").unwrap();
+        if !fields.is_empty() {
+            let lifetime = if fields.iter().any(|(_, _, ty)| ty.contains("'a")) { "<'a>" } else { "" };
+            writeln!(out, ",\n    /// {description}\n    struct Args{lifetime} {{").unwrap();
+            write_fields(&mut out, &fields);
+            write!(out, "    }}").unwrap();
+        }
+
+        if multi_output {
+            write!(out, " =>").unwrap();
+            if fields.is_empty() {
+                write!(out, "\n    /// {description}\n   ").unwrap();
+            }
+            writeln!(out, " struct Output {{").unwrap();
+            write_fields(&mut out, &outputs);
+            write!(out, "    }}").unwrap();
+        }
+        writeln!(out, "\n);").unwrap();
+
+        Ok(out)
+    }
+
+    fn generate(&mut self, operations: &[Operation]) -> String {
+        let mut operators = String::new();
+        for op in operations {
+            match self.operator(op) {
+                Ok(code) => {
+                    operators.push_str(&code);
+                    operators.push('\n');
+                },
+                Err(reason) => self.skipped.push((op.nickname.clone(), reason))
+            }
+        }
+
+        let mut enums = String::new();
+        let new_enums = std::mem::take(&mut self.new_enums);
+        for (c_name, g_type) in &new_enums {
+            enums.push_str(&vips_enum(c_name, *g_type));
+            enums.push('\n');
+        }
+
+        let version = unsafe { c_string(s::vips_version_string()) };
+        let (major, minor) = unsafe { (s::vips_version(0), s::vips_version(1)) };
+        let mut out = String::new();
+        writeln!(out, "// Generated by vips-gen from libvips {version}. Do not edit by hand, run").unwrap();
+        writeln!(out, "// `cargo run -p vips-gen` to regenerate.").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "// operation names are kept as they are in Vips, e.g. `LabQ2sRGB`").unwrap();
+        writeln!(out, "#![allow(non_snake_case)]").unwrap();
+        writeln!(out).unwrap();
+        if !enums.is_empty() {
+            writeln!(out, "use std::fmt;").unwrap();
+            writeln!(out, "use std::str::FromStr;").unwrap();
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "use vips_sys as s;").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "use crate::*;").unwrap();
+        if !enums.is_empty() {
+            writeln!(out, "use crate::enums::vips_enum;").unwrap();
+        }
+        writeln!(out).unwrap();
+        // the operators and enums may not exist in older versions, so fail
+        // with a clear message rather than with missing symbols
+        writeln!(out, "const _: () = assert!(").unwrap();
+        writeln!(
+            out,
+            "    s::VIPS_MAJOR_VERSION > {major} || (s::VIPS_MAJOR_VERSION == {major} && s::VIPS_MINOR_VERSION >= {minor}),"
+        ).unwrap();
+        writeln!(
+            out,
+            "    \"src/generated.rs needs libvips {major}.{minor} or later, run `cargo run -p vips-gen` to regenerate it\""
+        ).unwrap();
+        writeln!(out, ");").unwrap();
+        if !enums.is_empty() {
+            writeln!(out).unwrap();
+            out.push_str(&banner("Enums"));
+            writeln!(out).unwrap();
+            out.push_str(&enums);
+        }
+        writeln!(out).unwrap();
+        out.push_str(&banner("Operators"));
+        writeln!(out).unwrap();
+        out.push_str(&operators);
+
+        let skipped: Vec<_> = self.skipped.iter()
+            .filter(|(_, reason)| *reason != HAND_WRITTEN)
+            .collect();
+        if !skipped.is_empty() {
+            writeln!(out, "// Not generated:").unwrap();
+            for (nickname, reason) in skipped {
+                writeln!(out, "// - {nickname}: {reason}").unwrap();
+            }
+        }
+
+        out
+    }
+}
+
+fn write_fields(out: &mut String, fields: &[(&&Argument, String, String)]) {
+    let lines: Vec<String> = fields.iter().map(|(argument, name, ty)| {
+        let mut line = String::new();
+        let blurb = sentence(&argument.blurb);
+        if !blurb.is_empty() {
+            writeln!(line, "        /// {blurb}").unwrap();
+        }
+        write!(line, "        pub {name}: {ty}").unwrap();
+        line
+    }).collect();
+    writeln!(out, "{}", lines.join(",\n")).unwrap();
+}
+
+/// `name` as a Rust identifier, e.g. `r#match` for `match`. Returns `None` for
+/// names that can not be raw identifiers.
+fn identifier(name: &str) -> Option<String> {
+    match name {
+        "self" | "Self" | "super" | "crate" | "_" => None,
+        name if KEYWORDS.contains(&name) => Some(format!("r#{name}")),
+        name => Some(name.to_string())
+    }
+}
+
+/// Emit a `vips_enum!` call for the enum `c_name`
+fn vips_enum(c_name: &str, g_type: s::GType) -> String {
+    let name = c_name.strip_prefix("Vips").unwrap_or(c_name);
+    let mut variants = Vec::new();
+
+    unsafe {
+        let class = s::g_type_class_ref(g_type) as *mut s::GEnumClass;
+        for i in 0..(*class).n_values as usize {
+            let value = &*(*class).values.add(i);
+            let nick = c_string(value.value_nick);
+            // the `_LAST` members only mark the end of the C enums
+            if nick == "last" {
+                continue;
+            }
+            variants.push(format!(
+                "        {} = {c_name}_{} => \"{nick}\"",
+                variant_name(&nick),
+                c_string(value.value_name)
+            ));
+        }
+        s::g_type_class_unref(class as *mut c_void);
+    }
+
+    format!(
+        "vips_enum!(\n    {name}: s::{c_name} {{\n{}\n    }}\n);\n",
+        variants.join(",\n")
+    )
+}
+
+/// `"dest-over"` becomes `"DestOver"`
+fn variant_name(nick: &str) -> String {
+    let name: String = nick.split(['-', '_'])
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new()
+            }
+        })
+        .collect();
+
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("V{name}"),
+        _ => name
+    }
+}
+
+/// Capitalise `text` and strip newlines, for use as a one-line comment
+fn sentence(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new()
+    }
+}
+
+fn banner(title: &str) -> String {
+    let rule = format!("// {}", "=".repeat(77));
+    let heading = format!("// === {title} ");
+    format!("{rule}\n{heading}{}\n{rule}\n", "=".repeat(80 - heading.len()))
+}
+
+// =============================================================================
+// === Sources =================================================================
+// =============================================================================
+
+/// Identifiers following each occurrence of `marker` in `text`
+fn idents_after<'a>(text: &'a str, marker: &str) -> Vec<&'a str> {
+    text.match_indices(marker)
+        .map(|(i, _)| {
+            let rest = &text[i + marker.len()..];
+            let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            &rest[..end]
+        })
+        .filter(|ident| !ident.is_empty())
+        .collect()
+}
+
+fn hand_written_sources(src: &Path) -> Vec<String> {
+    let mut sources = Vec::new();
+    for entry in std::fs::read_dir(src).expect("Could not read source directory") {
+        let path = entry.expect("Could not read source directory").path();
+        if path.extension().is_some_and(|e| e == "rs")
+            && path.file_name().is_some_and(|n| n != "generated.rs")
+        {
+            sources.push(std::fs::read_to_string(&path).expect("Could not read source file"));
+        }
+    }
+    sources
+}
+
+/// Names of everything that would collide with a generated method
+fn taken_names(sources: &[String]) -> BTreeSet<String> {
+    let markers = [
+        "pub fn ",
+        "define_operator!(",
+        "define_multi_output_operator!(",
+        "define_array_operator!(",
+        "define_create_operator!("
+    ];
+
+    sources.iter()
+        .flat_map(|source| markers.iter().flat_map(move |m| idents_after(source, m)))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rust names of the enums defined with `vips_enum!`, by their C name
+fn known_enums(sources: &[String]) -> BTreeMap<String, String> {
+    let mut enums = BTreeMap::new();
+    for source in sources {
+        for (i, _) in source.match_indices(": s::Vips") {
+            let line_start = source[..i].rfind('\n').map_or(0, |p| p + 1);
+            let name = source[line_start..i].trim();
+            let c_name = idents_after(&source[i..], ": s::");
+            if let Some(c_name) = c_name.first() {
+                if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric()) {
+                    enums.insert(c_name.to_string(), name.to_string());
+                }
+            }
+        }
+    }
+    enums
+}
+
+// =============================================================================
+// === Main ====================================================================
+// =============================================================================
+
+fn main() {
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../src");
+
+    let argv_0 = CString::new("vips-gen").unwrap();
+    if unsafe { s::vips_init(argv_0.as_ptr()) } != 0 {
+        panic!("Could not initialise Vips");
+    }
+
+    let sources = hand_written_sources(&src);
+    let mut generator = Generator {
+        taken: taken_names(&sources),
+        known_enums: known_enums(&sources),
+        new_enums: BTreeMap::new(),
+        skipped: Vec::new()
+    };
+
+    let operations = unsafe { operations() };
+    let generated = generator.generate(&operations);
+
+    let out_path = src.join("generated.rs");
+    std::fs::write(&out_path, generated).expect("Could not write generated operators");
+
+    println!(
+        "Wrote {} operators to {}, skipped {}",
+        operations.len() - generator.skipped.len(),
+        out_path.display(),
+        generator.skipped.len()
+    );
+}